@@ -0,0 +1,86 @@
+// examples/binary-file-byte-freq.rs : example program that does a frequency analysis of the bytes in a (text or binary) file;
+
+use collect_rs::{
+    containers::{
+        ByteFrequencyMap,
+        FrequencyMap,
+    },
+    reporting::{
+        FrequencyReport,
+        ReportOptions,
+    },
+};
+
+use std::{
+    env as std_env,
+    fs as std_fs,
+    io as std_io,
+    process as std_process,
+};
+
+
+fn main() -> Result<(), std_io::Error> {
+    let mut process_path = None;
+    let mut input_path = None;
+
+    for arg in std_env::args() {
+        if "--help" == arg {
+            assert!(
+                process_path.is_some(),
+                "Rust runtime failed to provide program name as first element in `std::env::args`"
+            );
+
+            println!("USAGE: {} <input-path>", process_path.unwrap());
+
+            return Ok(());
+        } else {
+            match process_path {
+                None => {
+                    process_path = Some(arg);
+                },
+                Some(_) => {
+                    match input_path {
+                        None => {
+                            input_path = Some(arg);
+                        },
+                        Some(_) => {
+                            eprintln!("{}: too many arguments; use --help for usage", process_path.unwrap());
+
+                            std_process::exit(1);
+                        },
+                    }
+                },
+            }
+        }
+    }
+
+    match input_path {
+        Some(input_path) => process_file(&input_path),
+        None => {
+            eprintln!("{}: no input path specified; use --help for usage", process_path.unwrap());
+
+            std_process::exit(1);
+        },
+    }
+}
+
+fn process_file(input_path : &str) -> Result<(), std_io::Error> {
+    println!("processing '{input_path}'");
+
+    let f = std_fs::File::open(input_path)?;
+
+    let bfm = ByteFrequencyMap::from_reader(std_io::BufReader::new(f))?;
+
+    println!("results ({}, {}, {:.4} bits/byte):", bfm.len(), bfm.total(), bfm.entropy());
+
+    let fm = FrequencyMap::try_from(&bfm)
+        .map_err(|e| std_io::Error::new(std_io::ErrorKind::InvalidData, e))?
+        .map_keys(|b| format!("0x{b:02X}"));
+
+    FrequencyReport::from(&fm).write_table_io(&mut std_io::stdout(), &ReportOptions::default())?;
+
+    Ok(())
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
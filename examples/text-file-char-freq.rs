@@ -1,9 +1,16 @@
 // examples/text-file-char-freq.rs : example program that does a frequency analysis of characters in a (presumed to be) text file;
 
-use collect_rs::containers::UnicodePointMap;
+use collect_rs::{
+    containers::UnicodePointMap,
+    reporting::{
+        FrequencyReport,
+        ReportOptions,
+    },
+};
 
 use std::{
     env as std_env,
+    fmt as std_fmt,
     fs as std_fs,
     io as std_io,
     process as std_process,
@@ -11,6 +18,30 @@ use std::{
 };
 
 
+/// Displays a character as its code point followed by the character, or
+/// by its hexadecimal code point if it is a control character.
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(PartialOrd)]
+#[derive(Ord)]
+struct CodePoint(char);
+
+impl std_fmt::Display for CodePoint {
+    fn fmt(
+        &self,
+        f : &mut std_fmt::Formatter<'_>,
+    ) -> std_fmt::Result {
+        let c = self.0;
+
+        if c.is_control() {
+            write!(f, "{} {:#06x}", c as u32, c as u32)
+        } else {
+            write!(f, "{} {c}", c as u32)
+        }
+    }
+}
+
+
 fn main() -> Result<(), std_io::Error> {
     let mut process_path = None;
     let mut input_path = None;
@@ -71,17 +102,10 @@ fn process_file(
     }
 
     println!("results ({}, {}):", upm.len(), upm.total());
-    for (c, count) in upm.iter() {
-        println!(
-            "{} {} : {count}",
-            c as u32,
-            if c.is_control() {
-                format!("{:#06x}", c as u32)
-            } else {
-                c.to_string()
-            }
-        );
-    }
+
+    let records = upm.iter().map(|(c, count)| (CodePoint(c), count)).collect();
+
+    FrequencyReport::new(records, upm.total() as isize).write_table_io(&mut std_io::stdout(), &ReportOptions::default())?;
 
     Ok(())
 }
@@ -0,0 +1,480 @@
+// containers/arbitrary.rs : `FrequencyMapParameters`, `UnicodePointMapParameters`
+
+use super::{
+    FrequencyMap,
+    UnicodePointMap,
+};
+
+use core::{
+    cmp as std_cmp,
+    hash as std_hash,
+    ops as std_ops,
+};
+
+
+/// Parameters that control the generation of arbitrary [`FrequencyMap`]
+/// instances, for property-based testing with **proptest** and/or
+/// **quickcheck**.
+///
+/// Records whose generated count is 0 are not created, and keys that are
+/// generated more than once replace the earlier record, so the number of
+/// records may be less than that chosen from `len`.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub struct FrequencyMapParameters {
+    /// The range of the number of records to be generated.
+    pub len :    std_ops::Range<usize>,
+    /// The range of the count of each record, which may include negative
+    /// values.
+    pub counts : std_ops::RangeInclusive<isize>,
+}
+
+impl Default for FrequencyMapParameters {
+    /// Creates an instance that generates up to 31 records, each with a
+    /// count in the range [1, 100].
+    fn default() -> Self {
+        Self {
+            len :    0..32,
+            counts : 1..=100,
+        }
+    }
+}
+
+/// Parameters that control the generation of arbitrary
+/// [`UnicodePointMap`] instances, for property-based testing with
+/// **proptest** and/or **quickcheck**.
+///
+/// As for [`FrequencyMapParameters`], the number of records may be less
+/// than that chosen from `len`.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub struct UnicodePointMapParameters {
+    /// The range of the number of records to be generated.
+    pub len :     std_ops::Range<usize>,
+    /// The range of the count of each record, which may include negative
+    /// values.
+    pub counts :  std_ops::RangeInclusive<isize>,
+    /// The range of the default contiguous ceiling with which each
+    /// instance is created (see [`UnicodePointMap::new()`]).
+    pub ceiling : std_ops::RangeInclusive<char>,
+}
+
+impl Default for UnicodePointMapParameters {
+    /// Creates an instance that generates up to 31 records, each with a
+    /// count in the range [1, 100], in instances whose ceiling is in the
+    /// range [U+0000, U+0800].
+    fn default() -> Self {
+        Self {
+            len :     0..32,
+            counts :  1..=100,
+            ceiling : '\0'..='\u{800}',
+        }
+    }
+}
+
+
+// Implementation
+
+/// Builds a [`FrequencyMap`] from generated records, via the map's own
+/// insertion logic, so that all its invariants are respected.
+fn build_frequency_map_<K, I>(records : I) -> FrequencyMap<K>
+where
+    K : std_cmp::Eq + std_hash::Hash,
+    I : IntoIterator<Item = (K, isize)>,
+{
+    let mut fm = FrequencyMap::new();
+
+    for (key, count) in records {
+        fm.insert(key, count);
+    }
+
+    fm
+}
+
+/// Builds a [`UnicodePointMap`] from a generated ceiling and records, via
+/// the map's own insertion logic, so that all its invariants are
+/// respected.
+fn build_unicode_point_map_<I>(
+    ceiling : char,
+    records : I,
+) -> UnicodePointMap
+where
+    I : IntoIterator<Item = (char, isize)>,
+{
+    let mut upm = UnicodePointMap::new(ceiling);
+
+    for (c, count) in records {
+        upm.insert(c, count);
+    }
+
+    upm
+}
+
+
+// proptest
+
+#[cfg(feature = "proptest")]
+mod proptest_ {
+    use super::*;
+
+    use proptest::{
+        arbitrary::{
+            any,
+            Arbitrary,
+        },
+        collection,
+        strategy::{
+            BoxedStrategy,
+            Strategy,
+        },
+    };
+
+    use core::fmt as std_fmt;
+
+
+    /// Obtains a **proptest** strategy that generates [`FrequencyMap`]
+    /// instances whose keys are generated by `keys` and whose number of
+    /// records and counts are controlled by `parameters`.
+    pub fn frequency_map_strategy<S>(
+        keys : S,
+        parameters : FrequencyMapParameters,
+    ) -> impl Strategy<Value = FrequencyMap<S::Value>>
+    where
+        S : Strategy,
+        S::Value : std_cmp::Eq + std_hash::Hash,
+    {
+        collection::vec((keys, parameters.counts), parameters.len).prop_map(build_frequency_map_)
+    }
+
+    /// Obtains a **proptest** strategy that generates [`UnicodePointMap`]
+    /// instances whose code points are generated by `chars` and whose
+    /// number of records, counts, and ceiling are controlled by
+    /// `parameters`.
+    pub fn unicode_point_map_strategy<S>(
+        chars : S,
+        parameters : UnicodePointMapParameters,
+    ) -> impl Strategy<Value = UnicodePointMap>
+    where
+        S : Strategy<Value = char>,
+    {
+        let ceiling = proptest::char::range(*parameters.ceiling.start(), *parameters.ceiling.end());
+
+        (ceiling, collection::vec((chars, parameters.counts), parameters.len))
+            .prop_map(|(ceiling, records)| build_unicode_point_map_(ceiling, records))
+    }
+
+    impl<K> Arbitrary for FrequencyMap<K>
+    where
+        K : Arbitrary + std_cmp::Eq + std_hash::Hash + std_fmt::Debug + 'static,
+    {
+        type Parameters = FrequencyMapParameters;
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(parameters : Self::Parameters) -> Self::Strategy {
+            frequency_map_strategy(any::<K>(), parameters).boxed()
+        }
+    }
+
+    impl Arbitrary for UnicodePointMap {
+        type Parameters = UnicodePointMapParameters;
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(parameters : Self::Parameters) -> Self::Strategy {
+            unicode_point_map_strategy(any::<char>(), parameters).boxed()
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+pub use proptest_::{
+    frequency_map_strategy,
+    unicode_point_map_strategy,
+};
+
+
+// quickcheck
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck_ {
+    use super::*;
+
+    use quickcheck::{
+        Arbitrary,
+        Gen,
+    };
+
+    use std::boxed::Box;
+
+
+    /// Obtains an arbitrary [`FrequencyMap`], using **quickcheck**'s
+    /// generator `g`, whose number of records and counts are controlled
+    /// by `parameters`.
+    pub fn arbitrary_frequency_map<K>(
+        g : &mut Gen,
+        parameters : &FrequencyMapParameters,
+    ) -> FrequencyMap<K>
+    where
+        K : Arbitrary + std_cmp::Eq + std_hash::Hash,
+    {
+        let len = usize_in_(g, &parameters.len);
+        let mut fm = FrequencyMap::with_capacity(len);
+
+        for _ in 0..len {
+            let key = K::arbitrary(g);
+            let count = isize_in_(g, &parameters.counts);
+
+            fm.insert(key, count);
+        }
+
+        fm
+    }
+
+    /// Obtains an arbitrary [`UnicodePointMap`], using **quickcheck**'s
+    /// generator `g`, whose number of records, counts, and ceiling are
+    /// controlled by `parameters`.
+    pub fn arbitrary_unicode_point_map(
+        g : &mut Gen,
+        parameters : &UnicodePointMapParameters,
+    ) -> UnicodePointMap {
+        let ceiling = char_in_(g, &parameters.ceiling);
+        let len = usize_in_(g, &parameters.len);
+        let mut upm = UnicodePointMap::new(ceiling);
+
+        for _ in 0..len {
+            let c = char::arbitrary(g);
+            let count = isize_in_(g, &parameters.counts);
+
+            upm.insert(c, count);
+        }
+
+        upm
+    }
+
+    impl<K> Arbitrary for FrequencyMap<K>
+    where
+        K : Arbitrary + std_cmp::Eq + std_hash::Hash,
+    {
+        fn arbitrary(g : &mut Gen) -> Self {
+            arbitrary_frequency_map(g, &FrequencyMapParameters::default())
+        }
+
+        /// Shrinks by shrinking the records, which removes records and/or
+        /// shrinks their keys and counts.
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            let records : Vec<(K, isize)> = self.iter().map(|(key, &count)| (key.clone(), count)).collect();
+
+            Box::new(records.shrink().map(build_frequency_map_))
+        }
+    }
+
+    impl Arbitrary for UnicodePointMap {
+        fn arbitrary(g : &mut Gen) -> Self {
+            arbitrary_unicode_point_map(g, &UnicodePointMapParameters::default())
+        }
+
+        /// Shrinks by shrinking the records, which removes records and/or
+        /// shrinks their code points and counts, retaining the ceiling.
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            let empty = self.empty_like_();
+            let records : Vec<(char, isize)> = self.iter().collect();
+
+            Box::new(records.shrink().map(move |records| {
+                let mut upm = empty.clone();
+
+                for (c, count) in records {
+                    upm.insert(c, count);
+                }
+
+                upm
+            }))
+        }
+    }
+
+    /// Obtains a value in the half-open range `[start, start + extent)`, or
+    /// `start` if `extent` is 0.
+    fn u64_in_(
+        g : &mut Gen,
+        start : u64,
+        extent : u64,
+    ) -> u64 {
+        if 0 == extent {
+            start
+        } else {
+            start + u64::arbitrary(g) % extent
+        }
+    }
+
+    fn usize_in_(
+        g : &mut Gen,
+        range : &std_ops::Range<usize>,
+    ) -> usize {
+        if range.is_empty() {
+            range.start
+        } else {
+            u64_in_(g, range.start as u64, (range.end - range.start) as u64) as usize
+        }
+    }
+
+    fn isize_in_(
+        g : &mut Gen,
+        range : &std_ops::RangeInclusive<isize>,
+    ) -> isize {
+        if range.is_empty() {
+            return *range.start();
+        }
+
+        let start = *range.start() as i128;
+        let extent = *range.end() as i128 - start + 1;
+        let offset = (u128::from(u64::arbitrary(g)) % extent as u128) as i128;
+
+        (start + offset) as isize
+    }
+
+    fn char_in_(
+        g : &mut Gen,
+        range : &std_ops::RangeInclusive<char>,
+    ) -> char {
+        if range.is_empty() {
+            return *range.start();
+        }
+
+        let start = *range.start() as u32;
+        let end = *range.end() as u32;
+        let n = u64_in_(g, u64::from(start), u64::from(end - start) + 1) as u32;
+
+        // code points in the surrogate range are not valid `char`s
+        char::from_u32(n).unwrap_or(*range.end())
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+pub use quickcheck_::{
+    arbitrary_frequency_map,
+    arbitrary_unicode_point_map,
+};
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    #[cfg(feature = "proptest")]
+    mod proptest_ {
+        use super::super::*;
+
+        use proptest::prelude::*;
+
+
+        proptest! {
+            #[test]
+            fn TEST_FrequencyMap_Arbitrary_INVARIANTS(fm in any::<FrequencyMap<u8>>()) {
+                prop_assert!(fm.len() < 32);
+                prop_assert_eq!(Ok(()), fm.validate());
+                prop_assert_eq!(fm.total(), fm.iter().map(|(_, &count)| count).sum::<isize>());
+                prop_assert!(fm.iter().all(|(_, &count)| (1..=100).contains(&count)));
+            }
+
+            #[test]
+            fn TEST_frequency_map_strategy_WITH_NEGATIVE_COUNTS(
+                fm in frequency_map_strategy(
+                    "[a-z]{1,3}",
+                    FrequencyMapParameters {
+                        len :    0..8,
+                        counts : -5..=5,
+                    },
+                ),
+            ) {
+                prop_assert!(fm.len() < 8);
+                prop_assert_eq!(fm.total(), fm.iter().map(|(_, &count)| count).sum::<isize>());
+                prop_assert!(fm.iter().all(|(_, &count)| 0 != count && (-5..=5).contains(&count)));
+            }
+
+            #[test]
+            fn TEST_unicode_point_map_strategy_INVARIANTS(
+                upm in unicode_point_map_strategy(
+                    proptest::char::range('\0', '\u{3000}'),
+                    UnicodePointMapParameters {
+                        len :     0..64,
+                        counts :  -3..=3,
+                        ceiling : 'a'..='z',
+                    },
+                ),
+            ) {
+                prop_assert!(('a' as usize..='z' as usize).contains(&upm.contiguous_ceiling_()));
+                prop_assert_eq!(Ok(()), upm.validate());
+                prop_assert_eq!(upm.len(), upm.iter().count());
+                prop_assert_eq!(upm.total(), upm.iter().map(|(_, count)| count as i64).sum::<i64>());
+                prop_assert!(upm.iter().all(|(_, count)| 0 != count && (-3..=3).contains(&count)));
+            }
+        }
+    }
+
+    #[cfg(feature = "quickcheck")]
+    mod quickcheck_ {
+        use super::super::*;
+
+        use quickcheck::{
+            quickcheck,
+            Arbitrary,
+            Gen,
+        };
+
+
+        #[test]
+        fn TEST_arbitrary_frequency_map_WITH_NEGATIVE_COUNTS() {
+            let mut g = Gen::new(100);
+            let parameters = FrequencyMapParameters {
+                len :    0..10,
+                counts : -10..=-1,
+            };
+
+            for _ in 0..100 {
+                let fm : FrequencyMap<u16> = arbitrary_frequency_map(&mut g, &parameters);
+
+                assert!(fm.len() < 10);
+                assert!(fm.iter().all(|(_, &count)| (-10..=-1).contains(&count)));
+                assert_eq!(fm.total(), fm.iter().map(|(_, &count)| count).sum::<isize>());
+            }
+        }
+
+        #[test]
+        fn TEST_arbitrary_unicode_point_map_AND_shrink() {
+            let mut g = Gen::new(100);
+            let parameters = UnicodePointMapParameters {
+                len :     1..20,
+                counts :  1..=1,
+                ceiling : '\u{D000}'..='\u{E0FF}',
+            };
+
+            for _ in 0..100 {
+                let upm = arbitrary_unicode_point_map(&mut g, &parameters);
+
+                assert!((0xD000..=0xE0FF).contains(&upm.contiguous_ceiling_()));
+                assert_eq!(upm.total(), upm.len() as i64);
+
+                for shrunk in upm.shrink().take(10) {
+                    assert_eq!(upm.contiguous_ceiling_(), shrunk.contiguous_ceiling_());
+                    assert!(shrunk.len() <= upm.len());
+                }
+            }
+        }
+
+        #[test]
+        fn TEST_FrequencyMap_Arbitrary_PROPERTY() {
+            fn prop(fm : FrequencyMap<i8>) -> bool {
+                fm.total() == fm.iter().map(|(_, &count)| count).sum::<isize>()
+                    && fm.shrink().all(|shrunk| shrunk.len() <= fm.len())
+            }
+
+            quickcheck(prop as fn(FrequencyMap<i8>) -> bool);
+        }
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
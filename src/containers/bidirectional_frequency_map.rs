@@ -0,0 +1,520 @@
+// containers/bidirectional_frequency_map.rs : `BidirectionalFrequencyMap`
+
+use super::FrequencyMap;
+
+use crate::hash::{
+    hash_map,
+    HashSet,
+};
+
+use base_traits::{
+    IsEmpty,
+    Len,
+};
+
+use alloc::collections::BTreeMap;
+
+use core::{
+    borrow as std_borrow,
+    cmp as std_cmp,
+    hash as std_hash,
+    ops as std_ops,
+};
+
+
+/// A [`FrequencyMap`] that additionally maintains a reverse index from
+/// count to the keys having that count.
+///
+/// The reverse index is updated on every mutating operation, which allows
+/// the queries [`#keys_with_count()`](Self::keys_with_count),
+/// [`#count_of_counts()`](Self::count_of_counts),
+/// [`#max_count()`](Self::max_count), and
+/// [`#min_count()`](Self::min_count) to be answered in at most O(log n).
+///
+/// Because each key is stored in both the forward map and the reverse
+/// index, `K` must be [`Clone`].
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct BidirectionalFrequencyMap<K> {
+    /// The forward map of keys and counts.
+    fm : FrequencyMap<K>,
+    /// The reverse index of counts and the keys having that count.
+    index : BTreeMap<isize, HashSet<K>>,
+}
+
+// API functions
+
+impl<K> BidirectionalFrequencyMap<K> {
+    /// Creates a new empty instance.
+    pub fn new() -> Self {
+        let fm = FrequencyMap::new();
+        let index = BTreeMap::new();
+
+        Self {
+            fm,
+            index,
+        }
+    }
+
+    /// Creates a new empty instance with at least the given `capacity`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let fm = FrequencyMap::with_capacity(capacity);
+        let index = BTreeMap::new();
+
+        Self {
+            fm,
+            index,
+        }
+    }
+}
+
+// Mutating methods
+
+impl<K> BidirectionalFrequencyMap<K> {
+    /// Clears the map, removing all records and resets `#total()`.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.fm.clear();
+        self.index.clear();
+    }
+}
+
+impl<K: Clone + std_cmp::Eq + std_hash::Hash> BidirectionalFrequencyMap<K> {
+    /// Inserts a record with the given `key` and `count`, replacing any
+    /// existing record with that `key`. In the case that `count` is 0 any
+    /// existing record is removed and no record is created.
+    pub fn insert(
+        &mut self,
+        key: K,
+        count: isize,
+    ) -> Option<isize>
+    {
+        let prev = self.fm.get(&key);
+
+        self.reindex_(&key, prev, count);
+
+        self.fm.insert(key, count)
+    }
+
+    /// Updates the count by 1 of an existing record identified by `key`, or
+    /// creates, with a count of 1, a new record.
+    ///
+    /// In the case that the resulting count of an existing record is 0 then
+    /// the record is removed.
+    #[inline]
+    pub fn push(
+        &mut self,
+        key : K,
+    ) {
+        self.push_n(key, 1);
+    }
+
+    /// Updates the count by `count` of an existing record identifed by
+    /// `key`, or creates, with the given `count`, a new record.
+    ///
+    /// In the case that the resulting count of an existing record is 0 then
+    /// the record is removed.
+    pub fn push_n(
+        &mut self,
+        key : K,
+        count: isize,
+    ) {
+        if 0 != count {
+            let prev = self.fm.get(&key);
+
+            self.reindex_(&key, prev, prev + count);
+
+            self.fm.push_n(key, count);
+        }
+    }
+
+    /// Removes a key from the map, returning the count of the key if the
+    /// key was previously in the map.
+    pub fn remove<Q>(
+        &mut self,
+        key: &Q,
+    ) -> Option<isize>
+    where
+        K: std_borrow::Borrow<Q>,
+        Q: std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        let r = self.fm.remove(key);
+
+        if let Some(count) = r {
+            Self::unindex_(&mut self.index, key, count);
+        }
+
+        r
+    }
+}
+
+// Non-mutating methods
+
+impl<K : std_cmp::Eq + std_hash::Hash> BidirectionalFrequencyMap<K> {
+    /// Indicates whether a record exists for the given `key`.
+    #[inline]
+    pub fn contains_key<Q>(
+        &self,
+        key: &Q,
+    ) -> bool
+    where
+        K: std_borrow::Borrow<Q>,
+        Q: std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        self.fm.contains_key(key)
+    }
+
+    /// Obtains the count corresponding to the given `key`, obtaining 0 in
+    /// the case that no such record exists.
+    #[inline]
+    pub fn get<Q>(
+        &self,
+        key: &Q,
+    ) -> isize
+    where
+        K: std_borrow::Borrow<Q>,
+        Q: std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        self.fm.get(key)
+    }
+}
+
+impl<K> BidirectionalFrequencyMap<K> {
+    /// An iterator visiting all distinct counts, in ascending order,
+    /// along with the number of keys having that count, i.e. the
+    /// "frequency of frequencies" table. The iterator element type is
+    /// `(isize, usize)`.
+    #[inline]
+    pub fn count_of_counts(&self) -> impl Iterator<Item = (isize, usize)> + '_ {
+        self.index.iter().map(|(&count, keys)| (count, keys.len()))
+    }
+
+    /// An iterator visiting all key-count pairs in arbitrary order. The
+    /// iterator element type is `(&'a K, &'a isize)`.
+    #[inline]
+    pub fn iter(&self) -> hash_map::Iter<'_, K, isize> {
+        self.fm.iter()
+    }
+
+    /// An iterator visiting, in arbitrary order, all keys whose count is
+    /// exactly `count`.
+    pub fn keys_with_count(
+        &self,
+        count : isize,
+    ) -> impl Iterator<Item = &K> + '_ {
+        self.index.get(&count).into_iter().flat_map(|keys| keys.iter())
+    }
+
+    /// Obtains the greatest count of any record, or `None` if the map is
+    /// empty.
+    #[inline]
+    pub fn max_count(&self) -> Option<isize> {
+        self.index.last_key_value().map(|(&count, _)| count)
+    }
+
+    /// Obtains the smallest count of any record, or `None` if the map is
+    /// empty.
+    #[inline]
+    pub fn min_count(&self) -> Option<isize> {
+        self.index.first_key_value().map(|(&count, _)| count)
+    }
+
+    /// Obtains a reference to the underlying [`FrequencyMap`].
+    #[inline]
+    pub fn as_frequency_map(&self) -> &FrequencyMap<K> {
+        &self.fm
+    }
+}
+
+impl<K> BidirectionalFrequencyMap<K> {
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.fm.is_empty()
+    }
+
+    /// Obtains the number of records.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.fm.len()
+    }
+
+    /// Indicates the total frequency count across all records.
+    #[inline]
+    pub fn total(&self) -> isize {
+        self.fm.total()
+    }
+}
+
+// Implementation
+
+impl<K : Clone + std_cmp::Eq + std_hash::Hash> BidirectionalFrequencyMap<K> {
+    /// Moves `key` in the reverse index from `prev` to `curr`, where a
+    /// count of 0 denotes absence.
+    fn reindex_(
+        &mut self,
+        key : &K,
+        prev : isize,
+        curr : isize,
+    ) {
+        if prev != curr {
+            if 0 != prev {
+                Self::unindex_(&mut self.index, key, prev);
+            }
+            if 0 != curr {
+                self.index.entry(curr).or_default().insert(key.clone());
+            }
+        }
+    }
+}
+
+impl<K : std_cmp::Eq + std_hash::Hash> BidirectionalFrequencyMap<K> {
+    fn unindex_<Q>(
+        index : &mut BTreeMap<isize, HashSet<K>>,
+        key : &Q,
+        count : isize,
+    ) where
+        K: std_borrow::Borrow<Q>,
+        Q: std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        if let Some(keys) = index.get_mut(&count) {
+            keys.remove(key);
+
+            if keys.is_empty() {
+                index.remove(&count);
+            }
+        }
+    }
+}
+
+// Trait implementations
+
+impl<K> Default for BidirectionalFrequencyMap<K> {
+    /// Creates an empty instance.
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Clone + std_cmp::Eq + std_hash::Hash> From<FrequencyMap<K>> for BidirectionalFrequencyMap<K> {
+    /// Creates an instance from a [`FrequencyMap`], building the reverse
+    /// index from its records.
+    fn from(value: FrequencyMap<K>) -> Self {
+        let mut index : BTreeMap<isize, HashSet<K>> = BTreeMap::new();
+
+        for (key, &count) in value.iter() {
+            index.entry(count).or_default().insert(key.clone());
+        }
+
+        Self {
+            fm : value,
+            index,
+        }
+    }
+}
+
+impl<K: Clone + std_cmp::Eq + std_hash::Hash> FromIterator<K> for BidirectionalFrequencyMap<K> {
+    /// Creates an instance from an iterator of keys.
+    fn from_iter<T: IntoIterator<Item = K>>(iter: T) -> Self {
+        Self::from(FrequencyMap::from_iter(iter))
+    }
+}
+
+impl<K: Clone + std_cmp::Eq + std_hash::Hash, const N : usize> From<[(K, isize); N]> for BidirectionalFrequencyMap<K> {
+    /// Creates an instance from an array of key + count pairs.
+    fn from(value: [(K, isize); N]) -> Self {
+        Self::from(FrequencyMap::from(value))
+    }
+}
+
+impl<K: Clone + std_cmp::Eq + std_hash::Hash, const N : usize> From<[K; N]> for BidirectionalFrequencyMap<K> {
+    /// Creates an instance from an array of keys.
+    fn from(value: [K; N]) -> Self {
+        Self::from(FrequencyMap::from(value))
+    }
+}
+
+impl<K, Q> std_ops::Index<&Q> for BidirectionalFrequencyMap<K>
+where
+    K: std_cmp::Eq + std_hash::Hash + std_borrow::Borrow<Q>,
+    Q: std_cmp::Eq + std_hash::Hash + ?Sized,
+{
+    type Output = isize;
+
+    #[inline]
+    fn index(
+        &self,
+        key : &Q
+    ) -> &Self::Output {
+        &self.fm[key]
+    }
+}
+
+impl<K> IsEmpty for BidirectionalFrequencyMap<K> {
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.fm.is_empty()
+    }
+}
+
+impl<K> Len for BidirectionalFrequencyMap<K> {
+    /// Obtains the number of records.
+    #[inline]
+    fn len(&self) -> usize {
+        self.fm.len()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::BidirectionalFrequencyMap;
+
+
+    fn sorted_keys_with_count(
+        bfm : &BidirectionalFrequencyMap<&'static str>,
+        count : isize,
+    ) -> Vec<&'static str> {
+        let mut keys : Vec<_> = bfm.keys_with_count(count).copied().collect();
+
+        keys.sort();
+
+        keys
+    }
+
+    #[test]
+    fn TEST_BidirectionalFrequencyMap_Default() {
+
+        let bfm = BidirectionalFrequencyMap::<i32>::default();
+
+        assert!(bfm.is_empty());
+        assert_eq!(0, bfm.len());
+        assert_eq!(0, bfm.total());
+
+        assert_eq!(None, bfm.max_count());
+        assert_eq!(None, bfm.min_count());
+        assert_eq!(0, bfm.count_of_counts().count());
+        assert_eq!(0, bfm.keys_with_count(1).count());
+    }
+
+    #[test]
+    fn TEST_BidirectionalFrequencyMap_push_1() {
+
+        let mut bfm = BidirectionalFrequencyMap::default();
+
+        bfm.push("cat");
+        bfm.push("dog");
+        bfm.push("dog");
+        bfm.push("mouse");
+
+        assert!(!bfm.is_empty());
+        assert_eq!(3, bfm.len());
+        assert_eq!(4, bfm.total());
+
+        assert_eq!(1, bfm["cat"]);
+        assert_eq!(2, bfm["dog"]);
+
+        assert_eq!(vec!["cat", "mouse"], sorted_keys_with_count(&bfm, 1));
+        assert_eq!(vec!["dog"], sorted_keys_with_count(&bfm, 2));
+        assert_eq!(0, bfm.keys_with_count(3).count());
+
+        assert_eq!(vec![(1, 2), (2, 1)], bfm.count_of_counts().collect::<Vec<_>>());
+
+        assert_eq!(Some(2), bfm.max_count());
+        assert_eq!(Some(1), bfm.min_count());
+    }
+
+    #[test]
+    fn TEST_BidirectionalFrequencyMap_push_n_1() {
+
+        let mut bfm = BidirectionalFrequencyMap::default();
+
+        bfm.push_n("cat", 3);
+        bfm.push_n("dog", 3);
+        bfm.push_n("mouse", 0);
+
+        assert_eq!(2, bfm.len());
+        assert_eq!(6, bfm.total());
+        assert_eq!(vec!["cat", "dog"], sorted_keys_with_count(&bfm, 3));
+        assert_eq!(vec![(3, 2)], bfm.count_of_counts().collect::<Vec<_>>());
+
+        bfm.push_n("cat", -3);
+
+        assert_eq!(1, bfm.len());
+        assert_eq!(3, bfm.total());
+        assert!(!bfm.contains_key("cat"));
+        assert_eq!(vec!["dog"], sorted_keys_with_count(&bfm, 3));
+
+        bfm.push_n("dog", -5);
+
+        assert_eq!(1, bfm.len());
+        assert_eq!(-2, bfm.total());
+        assert_eq!(0, bfm.keys_with_count(3).count());
+        assert_eq!(vec!["dog"], sorted_keys_with_count(&bfm, -2));
+        assert_eq!(Some(-2), bfm.max_count());
+        assert_eq!(Some(-2), bfm.min_count());
+
+        bfm.push_n("dog", 2);
+
+        assert!(bfm.is_empty());
+        assert_eq!(None, bfm.max_count());
+        assert_eq!(None, bfm.min_count());
+    }
+
+    #[test]
+    fn TEST_BidirectionalFrequencyMap_insert_1() {
+
+        let mut bfm = BidirectionalFrequencyMap::from([
+            // insert list
+            ("cat", 1),
+            ("dog", 2),
+        ]);
+
+        assert_eq!(Some(1), bfm.insert("cat", 5));
+        assert_eq!(None, bfm.insert("mouse", 2));
+
+        assert_eq!(9, bfm.total());
+        assert_eq!(vec!["dog", "mouse"], sorted_keys_with_count(&bfm, 2));
+        assert_eq!(vec!["cat"], sorted_keys_with_count(&bfm, 5));
+        assert_eq!(0, bfm.keys_with_count(1).count());
+
+        assert_eq!(Some(2), bfm.insert("dog", 0));
+
+        assert_eq!(2, bfm.len());
+        assert_eq!(7, bfm.total());
+        assert_eq!(vec!["mouse"], sorted_keys_with_count(&bfm, 2));
+        assert_eq!(vec![(2, 1), (5, 1)], bfm.count_of_counts().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn TEST_BidirectionalFrequencyMap_remove_1() {
+
+        let mut bfm = BidirectionalFrequencyMap::from([
+            // insert list
+            "cat", "dog", "dog", "mouse", "mouse", "mouse",
+        ]);
+
+        assert_eq!(Some(3), bfm.max_count());
+
+        assert_eq!(Some(3), bfm.remove("mouse"));
+        assert_eq!(None, bfm.remove("mouse"));
+
+        assert_eq!(2, bfm.len());
+        assert_eq!(3, bfm.total());
+        assert_eq!(Some(2), bfm.max_count());
+        assert_eq!(Some(1), bfm.min_count());
+
+        bfm.clear();
+
+        assert!(bfm.is_empty());
+        assert_eq!(0, bfm.count_of_counts().count());
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
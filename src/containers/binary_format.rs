@@ -0,0 +1,796 @@
+// containers/binary_format.rs : compact versioned binary persistence
+
+use super::{
+    unicode_point_map::constants::MAXIMUM_VALID_CHAR_VALUE,
+    FrequencyMap,
+    UnicodePointMap,
+};
+
+use std::{
+    cmp as std_cmp,
+    error as std_error,
+    fmt as std_fmt,
+    hash as std_hash,
+    io as std_io,
+    io::{
+        Read as _,
+        Write as _,
+    },
+};
+
+
+/// Constants
+mod constants {
+
+    pub(super) const FREQUENCY_MAP_MAGIC : [u8; 4] = *b"CRFM";
+    pub(super) const UNICODE_POINT_MAP_MAGIC : [u8; 4] = *b"CRUP";
+
+    pub(super) const CURRENT_VERSION : u8 = 1;
+}
+
+
+/// Encodes and decodes keys for the binary persistence format.
+///
+/// Implementations are provided for the integer types, [`bool`], [`char`],
+/// and [`String`]. Integers are encoded as (zig-zag, for signed types)
+/// LEB128 varints.
+pub trait BinaryKey : Sized {
+    /// Writes the encoded form of `self` to `w`.
+    fn encode<W : std_io::Write>(
+        &self,
+        w : &mut W,
+    ) -> std_io::Result<()>;
+
+    /// Reads an encoded instance from `r`, failing with
+    /// [`std::io::ErrorKind::InvalidData`] if the encoding is invalid.
+    fn decode<R : std_io::Read>(r : &mut R) -> std_io::Result<Self>;
+}
+
+/// Error type for binary persistence loading.
+#[derive(Debug)]
+pub enum BinaryFormatError {
+    /// An error occurred reading the input, including any invalid key
+    /// encoding.
+    Io(std_io::Error),
+    /// The input does not begin with the expected magic number.
+    BadMagic,
+    /// The input is of a version that is not supported.
+    UnsupportedVersion(u8),
+    /// The stored checksum does not match that of the content.
+    ChecksumMismatch,
+    /// The stored total does not equal the sum of the stored counts.
+    TotalMismatch {
+        stored :   i64,
+        computed : i64,
+    },
+    /// The content is otherwise invalid, e.g. a zero count or a duplicated
+    /// key.
+    Malformed(&'static str),
+}
+
+impl std_fmt::Display for BinaryFormatError {
+    fn fmt(
+        &self,
+        f : &mut std_fmt::Formatter<'_>,
+    ) -> std_fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+            Self::BadMagic => f.write_str("bad magic number"),
+            Self::UnsupportedVersion(version) => write!(f, "unsupported version {version}"),
+            Self::ChecksumMismatch => f.write_str("checksum mismatch"),
+            Self::TotalMismatch {
+                stored,
+                computed,
+            } => write!(f, "stored total {stored} does not equal sum of counts {computed}"),
+            Self::Malformed(message) => write!(f, "malformed content: {message}"),
+        }
+    }
+}
+
+impl std_error::Error for BinaryFormatError {
+    fn source(&self) -> Option<&(dyn std_error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std_io::Error> for BinaryFormatError {
+    fn from(value : std_io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+
+// FrequencyMap
+
+impl<K : BinaryKey> FrequencyMap<K> {
+    /// Writes all records to `w` in the binary persistence format.
+    ///
+    /// The format comprises a magic number, a version, the record count and
+    /// total, each record's key (via [`BinaryKey`]) and count, and a
+    /// trailing CRC-32 of all preceding bytes. Records are written as they
+    /// are visited, so no intermediate buffer is materialised; `w` should
+    /// be buffered if it is not already.
+    pub fn write_binary<W : std_io::Write>(
+        &self,
+        w : W,
+    ) -> std_io::Result<()> {
+        let mut w = ChecksumWriter::new(w);
+
+        w.write_all(&constants::FREQUENCY_MAP_MAGIC)?;
+        w.write_all(&[constants::CURRENT_VERSION])?;
+        write_varint_(&mut w, self.len() as u64)?;
+        write_varint_(&mut w, zigzag_encode_(self.total() as i64))?;
+
+        for (key, &count) in self.iter() {
+            key.encode(&mut w)?;
+            write_varint_(&mut w, zigzag_encode_(count as i64))?;
+        }
+
+        w.finish()
+    }
+}
+
+impl<K : BinaryKey + std_cmp::Eq + std_hash::Hash> FrequencyMap<K> {
+    /// Reads an instance, in the binary persistence format as written by
+    /// [`#write_binary()`](Self::write_binary), from `r`.
+    ///
+    /// Records are decoded as they are read, so no intermediate buffer is
+    /// materialised; `r` should be buffered if it is not already.
+    ///
+    /// # Errors
+    /// In addition to I/O errors, fails if the magic number, version, or
+    /// checksum are not as expected, or if the stored total does not equal
+    /// the sum of the stored counts.
+    pub fn read_binary<R : std_io::Read>(r : R) -> Result<Self, BinaryFormatError> {
+        let mut r = ChecksumReader::new(r);
+
+        read_header_(&mut r, constants::FREQUENCY_MAP_MAGIC)?;
+
+        let len = read_len_(&mut r)?;
+        let stored_total = zigzag_decode_(read_varint_(&mut r)?);
+
+        let mut fm = Self::with_capacity(len.min(1 << 16));
+        let mut computed_total : i64 = 0;
+
+        for _ in 0..len {
+            let key = K::decode(&mut r)?;
+            let count = read_count_(&mut r)?;
+
+            if fm.contains_key(&key) {
+                return Err(BinaryFormatError::Malformed("duplicated key"));
+            }
+
+            computed_total = computed_total.wrapping_add(count as i64);
+
+            fm.insert(key, count);
+        }
+
+        r.verify()?;
+
+        check_total_(stored_total, computed_total)?;
+
+        Ok(fm)
+    }
+}
+
+
+// UnicodePointMap
+
+impl UnicodePointMap {
+    /// Reads an instance, in the binary persistence format as written by
+    /// [`#write_binary()`](Self::write_binary), from `r`. The instance has
+    /// the same contiguous ceiling as that written.
+    ///
+    /// Records are decoded as they are read, so no intermediate buffer is
+    /// materialised; `r` should be buffered if it is not already.
+    ///
+    /// # Errors
+    /// In addition to I/O errors, fails if the magic number, version, or
+    /// checksum are not as expected, or if the stored total does not equal
+    /// the sum of the stored counts.
+    pub fn read_binary<R : std_io::Read>(r : R) -> Result<Self, BinaryFormatError> {
+        let mut r = ChecksumReader::new(r);
+
+        read_header_(&mut r, constants::UNICODE_POINT_MAP_MAGIC)?;
+
+        let ceiling = read_varint_(&mut r)?;
+        let ceiling = u32::try_from(ceiling)
+            .ok()
+            .filter(|&ceiling| ceiling <= MAXIMUM_VALID_CHAR_VALUE)
+            .and_then(char::from_u32)
+            .ok_or(BinaryFormatError::Malformed("invalid contiguous ceiling"))?;
+
+        let len = read_len_(&mut r)?;
+        let stored_total = zigzag_decode_(read_varint_(&mut r)?);
+
+        let mut upm = Self::new(ceiling);
+        let mut computed_total : i64 = 0;
+
+        for _ in 0..len {
+            let c = char::decode(&mut r)?;
+            let count = read_count_(&mut r)?;
+
+            if upm.contains_key(&c) {
+                return Err(BinaryFormatError::Malformed("duplicated key"));
+            }
+
+            computed_total = computed_total.wrapping_add(count as i64);
+
+            upm.insert(c, count);
+        }
+
+        r.verify()?;
+
+        check_total_(stored_total, computed_total)?;
+
+        Ok(upm)
+    }
+
+    /// Writes all records to `w` in the binary persistence format.
+    ///
+    /// The format comprises a magic number, a version, the contiguous
+    /// ceiling, the record count and total, each record's code point and
+    /// count, and a trailing CRC-32 of all preceding bytes. Records are
+    /// written as they are visited, so no intermediate buffer is
+    /// materialised; `w` should be buffered if it is not already.
+    pub fn write_binary<W : std_io::Write>(
+        &self,
+        w : W,
+    ) -> std_io::Result<()> {
+        let mut w = ChecksumWriter::new(w);
+
+        w.write_all(&constants::UNICODE_POINT_MAP_MAGIC)?;
+        w.write_all(&[constants::CURRENT_VERSION])?;
+        write_varint_(&mut w, self.contiguous_ceiling_() as u64)?;
+        write_varint_(&mut w, self.len() as u64)?;
+        write_varint_(&mut w, zigzag_encode_(self.total()))?;
+
+        for (c, count) in self.iter() {
+            c.encode(&mut w)?;
+            write_varint_(&mut w, zigzag_encode_(count as i64))?;
+        }
+
+        w.finish()
+    }
+}
+
+
+// BinaryKey implementations
+
+macro_rules! impl_binary_key_for_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl BinaryKey for $t {
+                #[inline]
+                fn encode<W : std_io::Write>(
+                    &self,
+                    w : &mut W,
+                ) -> std_io::Result<()> {
+                    write_varint_(w, *self as u64)
+                }
+
+                fn decode<R : std_io::Read>(r : &mut R) -> std_io::Result<Self> {
+                    <$t>::try_from(read_varint_(r)?).map_err(|_| invalid_data_("integer out of range"))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_binary_key_for_signed {
+    ($($t:ty),*) => {
+        $(
+            impl BinaryKey for $t {
+                #[inline]
+                fn encode<W : std_io::Write>(
+                    &self,
+                    w : &mut W,
+                ) -> std_io::Result<()> {
+                    write_varint_(w, zigzag_encode_(*self as i64))
+                }
+
+                fn decode<R : std_io::Read>(r : &mut R) -> std_io::Result<Self> {
+                    <$t>::try_from(zigzag_decode_(read_varint_(r)?)).map_err(|_| invalid_data_("integer out of range"))
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_key_for_unsigned!(u8, u16, u32, u64, usize);
+impl_binary_key_for_signed!(i8, i16, i32, i64, isize);
+
+impl BinaryKey for bool {
+    #[inline]
+    fn encode<W : std_io::Write>(
+        &self,
+        w : &mut W,
+    ) -> std_io::Result<()> {
+        w.write_all(&[*self as u8])
+    }
+
+    fn decode<R : std_io::Read>(r : &mut R) -> std_io::Result<Self> {
+        match read_u8_(r)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid_data_("invalid bool")),
+        }
+    }
+}
+
+impl BinaryKey for char {
+    #[inline]
+    fn encode<W : std_io::Write>(
+        &self,
+        w : &mut W,
+    ) -> std_io::Result<()> {
+        write_varint_(w, *self as u64)
+    }
+
+    fn decode<R : std_io::Read>(r : &mut R) -> std_io::Result<Self> {
+        u32::try_from(read_varint_(r)?)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| invalid_data_("invalid char"))
+    }
+}
+
+impl BinaryKey for String {
+    fn encode<W : std_io::Write>(
+        &self,
+        w : &mut W,
+    ) -> std_io::Result<()> {
+        write_varint_(w, self.len() as u64)?;
+        w.write_all(self.as_bytes())
+    }
+
+    fn decode<R : std_io::Read>(r : &mut R) -> std_io::Result<Self> {
+        let len = read_varint_(r)?;
+        let mut bytes = Vec::new();
+
+        r.take(len).read_to_end(&mut bytes)?;
+
+        if bytes.len() as u64 != len {
+            return Err(std_io::ErrorKind::UnexpectedEof.into());
+        }
+
+        String::from_utf8(bytes).map_err(|_| invalid_data_("invalid UTF-8"))
+    }
+}
+
+
+// Implementation
+
+/// CRC-32 (IEEE 802.3, as used by zlib and PNG).
+mod crc32 {
+
+    const TABLE : [u32; 256] = {
+        let mut table = [0; 256];
+        let mut i = 0;
+
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut bit = 0;
+
+            while bit < 8 {
+                crc = if 0 != crc & 1 { 0xedb8_8320 ^ (crc >> 1) } else { crc >> 1 };
+                bit += 1;
+            }
+
+            table[i] = crc;
+            i += 1;
+        }
+
+        table
+    };
+
+    /// Updates the (non-inverted) running checksum `crc` with `bytes`.
+    pub(super) fn update(
+        crc : u32,
+        bytes : &[u8],
+    ) -> u32 {
+        let mut crc = !crc;
+
+        for &b in bytes {
+            crc = TABLE[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8);
+        }
+
+        !crc
+    }
+}
+
+struct ChecksumWriter<W> {
+    inner : W,
+    crc :   u32,
+}
+
+impl<W : std_io::Write> ChecksumWriter<W> {
+    fn new(inner : W) -> Self {
+        Self {
+            inner,
+            crc : 0,
+        }
+    }
+
+    /// Writes the checksum and flushes.
+    fn finish(mut self) -> std_io::Result<()> {
+        let crc = self.crc;
+
+        self.inner.write_all(&crc.to_le_bytes())?;
+        self.inner.flush()
+    }
+}
+
+impl<W : std_io::Write> std_io::Write for ChecksumWriter<W> {
+    fn write(
+        &mut self,
+        buf : &[u8],
+    ) -> std_io::Result<usize> {
+        let n = self.inner.write(buf)?;
+
+        self.crc = crc32::update(self.crc, &buf[..n]);
+
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std_io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+struct ChecksumReader<R> {
+    inner : R,
+    crc :   u32,
+}
+
+impl<R : std_io::Read> ChecksumReader<R> {
+    fn new(inner : R) -> Self {
+        Self {
+            inner,
+            crc : 0,
+        }
+    }
+
+    /// Reads the stored checksum and compares it with that of the content
+    /// read.
+    fn verify(mut self) -> Result<(), BinaryFormatError> {
+        let computed = self.crc;
+        let mut stored = [0; 4];
+
+        self.inner.read_exact(&mut stored)?;
+
+        if u32::from_le_bytes(stored) == computed {
+            Ok(())
+        } else {
+            Err(BinaryFormatError::ChecksumMismatch)
+        }
+    }
+}
+
+impl<R : std_io::Read> std_io::Read for ChecksumReader<R> {
+    fn read(
+        &mut self,
+        buf : &mut [u8],
+    ) -> std_io::Result<usize> {
+        let n = self.inner.read(buf)?;
+
+        self.crc = crc32::update(self.crc, &buf[..n]);
+
+        Ok(n)
+    }
+}
+
+fn check_total_(
+    stored : i64,
+    computed : i64,
+) -> Result<(), BinaryFormatError> {
+    if stored == computed {
+        Ok(())
+    } else {
+        Err(BinaryFormatError::TotalMismatch {
+            stored,
+            computed,
+        })
+    }
+}
+
+fn invalid_data_(message : &'static str) -> std_io::Error {
+    std_io::Error::new(std_io::ErrorKind::InvalidData, message)
+}
+
+fn read_count_<R : std_io::Read>(r : &mut R) -> Result<isize, BinaryFormatError> {
+    let count = isize::try_from(zigzag_decode_(read_varint_(r)?))
+        .map_err(|_| BinaryFormatError::Malformed("count out of range"))?;
+
+    if 0 == count {
+        Err(BinaryFormatError::Malformed("zero count"))
+    } else {
+        Ok(count)
+    }
+}
+
+fn read_header_<R : std_io::Read>(
+    r : &mut R,
+    expected_magic : [u8; 4],
+) -> Result<(), BinaryFormatError> {
+    let mut magic = [0; 4];
+
+    r.read_exact(&mut magic)?;
+
+    if magic != expected_magic {
+        return Err(BinaryFormatError::BadMagic);
+    }
+
+    match read_u8_(r)? {
+        constants::CURRENT_VERSION => Ok(()),
+        version => Err(BinaryFormatError::UnsupportedVersion(version)),
+    }
+}
+
+fn read_len_<R : std_io::Read>(r : &mut R) -> Result<usize, BinaryFormatError> {
+    usize::try_from(read_varint_(r)?).map_err(|_| BinaryFormatError::Malformed("record count out of range"))
+}
+
+fn read_u8_<R : std_io::Read>(r : &mut R) -> std_io::Result<u8> {
+    let mut b = [0; 1];
+
+    r.read_exact(&mut b)?;
+
+    Ok(b[0])
+}
+
+fn read_varint_<R : std_io::Read>(r : &mut R) -> std_io::Result<u64> {
+    let mut value = 0u64;
+
+    for shift in (0..64).step_by(7) {
+        let b = read_u8_(r)?;
+        let bits = (b & 0x7f) as u64;
+
+        if 63 == shift && bits > 1 {
+            break;
+        }
+
+        value |= bits << shift;
+
+        if 0 == b & 0x80 {
+            return Ok(value);
+        }
+    }
+
+    Err(invalid_data_("varint overflow"))
+}
+
+fn write_varint_<W : std_io::Write>(
+    w : &mut W,
+    mut value : u64,
+) -> std_io::Result<()> {
+    let mut buf = [0; 10];
+    let mut n = 0;
+
+    loop {
+        let b = (value & 0x7f) as u8;
+
+        value >>= 7;
+
+        if 0 == value {
+            buf[n] = b;
+            n += 1;
+
+            break;
+        }
+
+        buf[n] = b | 0x80;
+        n += 1;
+    }
+
+    w.write_all(&buf[..n])
+}
+
+#[inline]
+fn zigzag_decode_(value : u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+#[inline]
+fn zigzag_encode_(value : i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::{
+        BinaryFormatError,
+        BinaryKey,
+    };
+
+    use crate::containers::{
+        FrequencyMap,
+        UnicodePointMap,
+    };
+
+
+    fn round_trip<K : BinaryKey + PartialEq + std::fmt::Debug>(key : K) {
+        let mut v = Vec::new();
+
+        key.encode(&mut v).unwrap();
+
+        assert_eq!(key, K::decode(&mut v.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn TEST_BinaryKey_ROUND_TRIP() {
+        round_trip(0u8);
+        round_trip(u8::MAX);
+        round_trip(u64::MAX);
+        round_trip(i64::MIN);
+        round_trip(i64::MAX);
+        round_trip(-1i32);
+        round_trip(true);
+        round_trip('🐻');
+        round_trip(String::new());
+        round_trip("héllo".to_string());
+
+        assert!(u8::decode(&mut [0x80, 0x02].as_slice()).is_err());
+        assert!(char::decode(&mut [0x80, 0xb0, 0x03].as_slice()).is_err());
+        assert!(u64::decode(&mut [0xff; 11].as_slice()).is_err());
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_write_binary_1() {
+        let fm = FrequencyMap::<String>::from([
+            // insert list
+            ("cat".to_string(), 3),
+            ("dog".to_string(), -1),
+            ("mouse".to_string(), 1_000_000),
+        ]);
+
+        let mut v = Vec::new();
+
+        fm.write_binary(&mut v).unwrap();
+
+        assert_eq!(b"CRFM\x01\x03", &v[..6]);
+
+        let fm2 = FrequencyMap::<String>::read_binary(v.as_slice()).unwrap();
+
+        assert_eq!(3, fm2.len());
+        assert_eq!(fm.total(), fm2.total());
+        assert_eq!(3, fm2["cat"]);
+        assert_eq!(-1, fm2["dog"]);
+        assert_eq!(1_000_000, fm2["mouse"]);
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_read_binary_EMPTY() {
+        let fm = FrequencyMap::<i32>::new();
+
+        let mut v = Vec::new();
+
+        fm.write_binary(&mut v).unwrap();
+
+        let fm2 = FrequencyMap::<i32>::read_binary(v.as_slice()).unwrap();
+
+        assert!(fm2.is_empty());
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_read_binary_ERRORS() {
+        let fm = FrequencyMap::<i32>::from([1, 2, 2]);
+
+        let mut v = Vec::new();
+
+        fm.write_binary(&mut v).unwrap();
+
+        {
+            let mut v = v.clone();
+
+            v[0] = b'X';
+
+            assert!(matches!(
+                FrequencyMap::<i32>::read_binary(v.as_slice()),
+                Err(BinaryFormatError::BadMagic)
+            ));
+        }
+
+        {
+            let mut v = v.clone();
+
+            v[4] = 99;
+
+            assert!(matches!(
+                FrequencyMap::<i32>::read_binary(v.as_slice()),
+                Err(BinaryFormatError::UnsupportedVersion(99))
+            ));
+        }
+
+        {
+            let mut v = v.clone();
+            let last = v.len() - 1;
+
+            v[last] ^= 0xff;
+
+            assert!(matches!(
+                FrequencyMap::<i32>::read_binary(v.as_slice()),
+                Err(BinaryFormatError::ChecksumMismatch)
+            ));
+        }
+
+        {
+            assert!(matches!(
+                FrequencyMap::<i32>::read_binary(&v[..v.len() - 2]),
+                Err(BinaryFormatError::Io(_))
+            ));
+        }
+
+        {
+            assert!(matches!(
+                UnicodePointMap::read_binary(v.as_slice()),
+                Err(BinaryFormatError::BadMagic)
+            ));
+        }
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_read_binary_TOTAL_MISMATCH() {
+        // hand-crafted content with a total (5) not matching the counts
+        let mut v = Vec::new();
+
+        v.extend_from_slice(b"CRFM\x01");
+        v.push(1); // len
+        v.push(10); // total 5, zig-zag encoded
+        v.push(7); // key
+        v.push(2); // count 1, zig-zag encoded
+
+        let crc = super::crc32::update(0, &v);
+
+        v.extend_from_slice(&crc.to_le_bytes());
+
+        match FrequencyMap::<u8>::read_binary(v.as_slice()) {
+            Err(BinaryFormatError::TotalMismatch {
+                stored,
+                computed,
+            }) => {
+                assert_eq!(5, stored);
+                assert_eq!(1, computed);
+            },
+            r => panic!("unexpected result: {r:?}"),
+        }
+    }
+
+    #[test]
+    fn TEST_UnicodePointMap_write_binary_1() {
+        let mut upm = UnicodePointMap::new('\u{100}');
+
+        upm.push_n('a', 3);
+        upm.push_n('é', -2);
+        upm.push_n('🐻', 5);
+
+        let mut v = Vec::new();
+
+        upm.write_binary(&mut v).unwrap();
+
+        let upm2 = UnicodePointMap::read_binary(v.as_slice()).unwrap();
+
+        assert_eq!(3, upm2.len());
+        assert_eq!(6, upm2.total());
+        assert!(upm2.capacity() >= 256);
+        assert_eq!(3, upm2['a']);
+        assert_eq!(-2, upm2['é']);
+        assert_eq!(5, upm2['🐻']);
+    }
+
+    #[test]
+    fn TEST_crc32_KNOWN_VALUE() {
+        assert_eq!(0xcbf4_3926, super::crc32::update(0, b"123456789"));
+        assert_eq!(0xcbf4_3926, super::crc32::update(super::crc32::update(0, b"1234"), b"56789"));
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
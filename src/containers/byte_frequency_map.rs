@@ -0,0 +1,440 @@
+// containers/byte_frequency_map.rs : `ByteFrequencyMap`
+
+use super::FrequencyMap;
+
+use base_traits::{
+    IsEmpty,
+    Len,
+};
+
+use core::{
+    fmt as std_fmt,
+    ops as std_ops,
+};
+
+#[cfg(feature = "std")]
+use std::io as std_io;
+
+
+/// Slices shorter than this are counted directly, rather than via the
+/// multi-table strategy of [`ByteFrequencyMap::push_bytes()`].
+const MULTI_TABLE_THRESHOLD : usize = 256;
+
+
+/// Error type for the conversion of a [`ByteFrequencyMap`] into a
+/// [`FrequencyMap<u8>`], whose counts, and total, are `isize`.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub struct ByteCountOverflowError {
+    /// The total of the [`ByteFrequencyMap`], which exceeds `isize::MAX`.
+    pub total : u64,
+}
+
+impl std_fmt::Display for ByteCountOverflowError {
+    fn fmt(
+        &self,
+        f : &mut std_fmt::Formatter<'_>,
+    ) -> std_fmt::Result {
+        write!(f, "total byte count {} exceeds `isize::MAX`", self.total)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ByteCountOverflowError {}
+
+
+/// A container that measures the frequencies of byte values, using fixed
+/// storage of one count per byte value.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub struct ByteFrequencyMap {
+    /// The count of each byte value.
+    counts : [u64; 256],
+    /// The total number of bytes represented.
+    total :  u64,
+}
+
+// API functions
+
+impl ByteFrequencyMap {
+    /// Creates a new empty instance.
+    pub fn new() -> Self {
+        Self {
+            counts : [0; 256],
+            total :  0,
+        }
+    }
+
+    /// Creates an instance from the bytes obtained from `r` until it is
+    /// exhausted.
+    #[cfg(feature = "std")]
+    pub fn from_reader<R : std_io::Read>(mut r : R) -> std_io::Result<Self> {
+        let mut bfm = Self::new();
+        let mut buf = vec![0; 64 * 1024];
+
+        loop {
+            match r.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => bfm.push_bytes(&buf[.. n]),
+                Err(e) if std_io::ErrorKind::Interrupted == e.kind() => {},
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(bfm)
+    }
+}
+
+// Mutating methods
+
+impl ByteFrequencyMap {
+    /// Clears the map, removing all records and resets `#total()`.
+    pub fn clear(&mut self) {
+        self.counts = [0; 256];
+        self.total = 0;
+    }
+
+    /// Updates the count of the byte value `b` by 1.
+    #[inline]
+    pub fn push(
+        &mut self,
+        b : u8,
+    ) {
+        self.push_n(b, 1);
+    }
+
+    /// Updates the count of every byte in `bytes`.
+    ///
+    /// Long slices are counted into several independent tables that are
+    /// subsequently summed, so that runs of the same byte value do not
+    /// serialise on a single counter.
+    pub fn push_bytes(
+        &mut self,
+        bytes : &[u8],
+    ) {
+        if bytes.len() < MULTI_TABLE_THRESHOLD {
+            for &b in bytes {
+                self.counts[b as usize] += 1;
+            }
+        } else {
+            let mut tables = [[0u64; 256]; 4];
+
+            let chunks = bytes.chunks_exact(4);
+            let remainder = chunks.remainder();
+
+            for chunk in chunks {
+                tables[0][chunk[0] as usize] += 1;
+                tables[1][chunk[1] as usize] += 1;
+                tables[2][chunk[2] as usize] += 1;
+                tables[3][chunk[3] as usize] += 1;
+            }
+
+            for &b in remainder {
+                tables[0][b as usize] += 1;
+            }
+
+            for (ix, count) in self.counts.iter_mut().enumerate() {
+                *count += tables[0][ix] + tables[1][ix] + tables[2][ix] + tables[3][ix];
+            }
+        }
+
+        self.total += bytes.len() as u64;
+    }
+
+    /// Updates the count of the byte value `b` by `count`.
+    #[inline]
+    pub fn push_n(
+        &mut self,
+        b : u8,
+        count : u64,
+    ) {
+        self.counts[b as usize] += count;
+        self.total += count;
+    }
+}
+
+// Non-mutating methods
+
+impl ByteFrequencyMap {
+    /// Obtains the count of each byte value.
+    #[inline]
+    pub fn counts(&self) -> &[u64; 256] {
+        &self.counts
+    }
+
+    /// Obtains the Shannon entropy, in bits per byte, of the byte values
+    /// represented, which is 0 if the instance is empty.
+    #[cfg(feature = "std")]
+    pub fn entropy(&self) -> f64 {
+        if 0 == self.total {
+            return 0.0;
+        }
+
+        let total = self.total as f64;
+
+        -self
+            .counts
+            .iter()
+            .filter(|&&count| 0 != count)
+            .map(|&count| {
+                let p = count as f64 / total;
+
+                p * p.log2()
+            })
+            .sum::<f64>()
+    }
+
+    /// Obtains the count of the byte value `b`.
+    #[inline]
+    pub fn get(
+        &self,
+        b : u8,
+    ) -> u64 {
+        self.counts[b as usize]
+    }
+
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.is_empty_()
+    }
+
+    /// An iterator visiting, in ascending order, each byte value that has a
+    /// non-zero count, along with its count. The iterator element type is
+    /// `(u8, u64)`.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, u64)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| 0 != count)
+            .map(|(ix, &count)| (ix as u8, count))
+    }
+
+    /// Obtains the number of byte values that have a non-zero count.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len_()
+    }
+
+    /// Indicates the total frequency count across all byte values.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+// Implementation
+
+impl ByteFrequencyMap {
+    #[inline]
+    fn is_empty_(&self) -> bool {
+        0 == self.total
+    }
+
+    fn len_(&self) -> usize {
+        self.counts.iter().filter(|&&count| 0 != count).count()
+    }
+}
+
+// Trait implementations
+
+impl Default for ByteFrequencyMap {
+    /// Creates an empty instance.
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<&ByteFrequencyMap> for FrequencyMap<u8> {
+    type Error = ByteCountOverflowError;
+
+    /// Creates a [`FrequencyMap`] from the byte values, and their counts,
+    /// of the given instance.
+    ///
+    /// # Errors
+    ///
+    /// [`ByteCountOverflowError`] if the total exceeds `isize::MAX`, as it
+    /// may on 32-bit targets for inputs of more than 2 GiB; if the total
+    /// does not then neither does any count.
+    fn try_from(value : &ByteFrequencyMap) -> Result<Self, Self::Error> {
+        if isize::try_from(value.total()).is_err() {
+            return Err(ByteCountOverflowError {
+                total : value.total(),
+            });
+        }
+
+        let mut fm = FrequencyMap::with_capacity(value.len());
+
+        for (b, count) in value.iter() {
+            fm.insert(b, count as isize);
+        }
+
+        Ok(fm)
+    }
+}
+
+impl FromIterator<u8> for ByteFrequencyMap {
+    /// Creates an instance from an iterator of bytes.
+    fn from_iter<T : IntoIterator<Item = u8>>(iter : T) -> Self {
+        let mut bfm = Self::new();
+
+        for b in iter {
+            bfm.push(b);
+        }
+
+        bfm
+    }
+}
+
+impl std_ops::Index<u8> for ByteFrequencyMap {
+    type Output = u64;
+
+    #[inline]
+    fn index(
+        &self,
+        b : u8,
+    ) -> &Self::Output {
+        &self.counts[b as usize]
+    }
+}
+
+impl IsEmpty for ByteFrequencyMap {
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.is_empty_()
+    }
+}
+
+impl Len for ByteFrequencyMap {
+    /// Obtains the number of byte values that have a non-zero count.
+    #[inline]
+    fn len(&self) -> usize {
+        self.len_()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::{
+        ByteCountOverflowError,
+        ByteFrequencyMap,
+    };
+
+    use crate::containers::FrequencyMap;
+
+
+    fn make_bytes(n : usize) -> Vec<u8> {
+        // deterministic, skewed, pseudo-random bytes
+        let mut x = 0x2545_F491_u32;
+
+        (0..n)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+
+                (x % 7) as u8 * (x % 5) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn TEST_ByteFrequencyMap_Default() {
+        let bfm = ByteFrequencyMap::default();
+
+        assert!(bfm.is_empty());
+        assert_eq!(0, bfm.len());
+        assert_eq!(0, bfm.total());
+        assert_eq!(0, bfm[b'a']);
+        assert_eq!(0, bfm.iter().count());
+    }
+
+    #[test]
+    fn TEST_ByteFrequencyMap_push_bytes_MATCHES_push() {
+        for n in [0, 1, 7, 255, 256, 257, 4099] {
+            let bytes = make_bytes(n);
+
+            let mut bfm = ByteFrequencyMap::new();
+
+            bfm.push_bytes(&bytes);
+
+            let expected : ByteFrequencyMap = bytes.iter().copied().collect();
+
+            assert_eq!(expected, bfm);
+            assert_eq!(n as u64, bfm.total());
+        }
+    }
+
+    #[test]
+    fn TEST_ByteFrequencyMap_INTO_FrequencyMap() {
+        let mut bfm = ByteFrequencyMap::new();
+
+        bfm.push_bytes(b"mississippi");
+        bfm.push_n(0, 3);
+
+        assert_eq!(5, bfm.len());
+        assert_eq!(14, bfm.total());
+        assert_eq!(vec![(0, 3), (b'i', 4), (b'm', 1), (b'p', 2), (b's', 4)], bfm.iter().collect::<Vec<_>>());
+
+        let fm = FrequencyMap::try_from(&bfm).unwrap();
+
+        assert_eq!(5, fm.len());
+        assert_eq!(14, fm.total());
+        assert_eq!(4, fm[&b's']);
+    }
+
+    #[test]
+    fn TEST_ByteFrequencyMap_INTO_FrequencyMap_OVERFLOW() {
+        let mut bfm = ByteFrequencyMap::new();
+
+        bfm.push_n(b'a', isize::MAX as u64);
+
+        assert_eq!(Ok(isize::MAX), FrequencyMap::try_from(&bfm).map(|fm| fm[&b'a']));
+
+        // no single count exceeds `isize::MAX`, but their total does
+        bfm.push_n(b'b', 1);
+
+        let e = FrequencyMap::try_from(&bfm).unwrap_err();
+
+        assert_eq!(
+            ByteCountOverflowError {
+                total : isize::MAX as u64 + 1,
+            },
+            e
+        );
+        assert_eq!(format!("total byte count {} exceeds `isize::MAX`", isize::MAX as u64 + 1), e.to_string());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn TEST_ByteFrequencyMap_from_reader_AND_entropy() {
+        let bytes : Vec<u8> = (0..=255).cycle().take(256 * 1000 + 17).collect();
+
+        let bfm = ByteFrequencyMap::from_reader(bytes.as_slice()).unwrap();
+
+        assert_eq!(bytes.len() as u64, bfm.total());
+        assert_eq!(1001, bfm[16]);
+        assert_eq!(1000, bfm[17]);
+
+        let uniform = ByteFrequencyMap::from_reader(&(0..=255).collect::<Vec<u8>>()[..]).unwrap();
+
+        assert!((8.0 - uniform.entropy()).abs() < 1e-12);
+        assert_eq!(0.0, ByteFrequencyMap::from_iter(*b"aaaa").entropy());
+        assert_eq!(1.0, ByteFrequencyMap::from_iter(*b"abab").entropy());
+        assert_eq!(0.0, ByteFrequencyMap::new().entropy());
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
@@ -0,0 +1,409 @@
+// containers/conditional_frequency_map.rs : `ConditionalFrequencyMap`
+
+use super::FrequencyMap;
+
+use crate::hash::{
+    hash_map::{
+        self,
+        Entry,
+    },
+    HashMap,
+};
+
+use base_traits::{
+    IsEmpty,
+    Len,
+};
+
+use core::{
+    borrow as std_borrow,
+    cmp as std_cmp,
+    hash as std_hash,
+};
+
+
+/// A container that measures the frequencies of outcomes (of type `K`)
+/// under each of a number of conditions (of type `C`), from which
+/// conditional probabilities may be obtained.
+///
+/// The frequencies of each outcome across all conditions are maintained
+/// as a marginal [`FrequencyMap`], as is the overall total.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct ConditionalFrequencyMap<C, K> {
+    /// The outcome frequencies for each condition.
+    conditions : HashMap<C, FrequencyMap<K>>,
+    /// The outcome frequencies across all conditions.
+    marginal :   FrequencyMap<K>,
+}
+
+// API functions
+
+impl<C, K> ConditionalFrequencyMap<C, K> {
+    /// Creates a new empty instance.
+    pub fn new() -> Self {
+        Self {
+            conditions : HashMap::new(),
+            marginal :   FrequencyMap::new(),
+        }
+    }
+}
+
+// Mutating methods
+
+impl<C, K> ConditionalFrequencyMap<C, K> {
+    /// Clears the map, removing all records and resets `#total()`.
+    pub fn clear(&mut self) {
+        self.conditions.clear();
+        self.marginal.clear();
+    }
+}
+
+impl<C, K> ConditionalFrequencyMap<C, K>
+where
+    C : std_cmp::Eq + std_hash::Hash,
+    K : Clone + std_cmp::Eq + std_hash::Hash,
+{
+    /// Updates the count by 1 of the outcome `key` under the condition
+    /// `cond`.
+    #[inline]
+    pub fn push(
+        &mut self,
+        cond : C,
+        key : K,
+    ) {
+        self.push_n(cond, key, 1);
+    }
+
+    /// Updates the count by `count` of the outcome `key` under the
+    /// condition `cond`.
+    ///
+    /// In the case that a condition thereby has no remaining records then
+    /// the condition is removed.
+    pub fn push_n(
+        &mut self,
+        cond : C,
+        key : K,
+        count : isize,
+    ) {
+        if 0 != count {
+            match self.conditions.entry(cond) {
+                Entry::Occupied(mut e) => {
+                    e.get_mut().push_n(key.clone(), count);
+
+                    if e.get().is_empty() {
+                        e.remove();
+                    }
+                },
+                Entry::Vacant(e) => {
+                    e.insert(FrequencyMap::new()).push_n(key.clone(), count);
+                },
+            }
+
+            self.marginal.push_n(key, count);
+        }
+    }
+}
+
+// Non-mutating methods
+
+impl<C, K> ConditionalFrequencyMap<C, K>
+where
+    C : std_cmp::Eq + std_hash::Hash,
+    K : std_cmp::Eq + std_hash::Hash,
+{
+    /// Obtains the outcome frequencies for the given condition, or `None`
+    /// if no such condition exists.
+    pub fn condition<Q>(
+        &self,
+        cond : &Q,
+    ) -> Option<&FrequencyMap<K>>
+    where
+        C : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        self.conditions.get(cond)
+    }
+
+    /// Obtains the total frequency count of the given condition, obtaining
+    /// 0 in the case that no such condition exists.
+    pub fn condition_total<Q>(
+        &self,
+        cond : &Q,
+    ) -> isize
+    where
+        C : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        self.conditions.get(cond).map_or(0, FrequencyMap::total)
+    }
+
+    /// Indicates whether a record exists for the given condition.
+    #[inline]
+    pub fn contains_condition<Q>(
+        &self,
+        cond : &Q,
+    ) -> bool
+    where
+        C : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        self.conditions.contains_key(cond)
+    }
+
+    /// Obtains the count of the outcome `key` under the condition `cond`,
+    /// obtaining 0 in the case that no such record exists.
+    pub fn get<QC, QK>(
+        &self,
+        cond : &QC,
+        key : &QK,
+    ) -> isize
+    where
+        C : std_borrow::Borrow<QC>,
+        K : std_borrow::Borrow<QK>,
+        QC : std_hash::Hash + std_cmp::Eq + ?Sized,
+        QK : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        self.conditions.get(cond).map_or(0, |fm| fm.get(key))
+    }
+
+    /// Obtains the probability of the outcome `key` across all conditions,
+    /// or `None` if the total is not positive.
+    pub fn marginal_probability<Q>(
+        &self,
+        key : &Q,
+    ) -> Option<f64>
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        Self::ratio_(self.marginal.get(key), self.marginal.total())
+    }
+
+    /// Obtains the conditional probability of the outcome `key` given the
+    /// condition `cond`, i.e. P(`key` | `cond`), or `None` if the total of
+    /// the condition is not positive.
+    pub fn probability<QC, QK>(
+        &self,
+        cond : &QC,
+        key : &QK,
+    ) -> Option<f64>
+    where
+        C : std_borrow::Borrow<QC>,
+        K : std_borrow::Borrow<QK>,
+        QC : std_hash::Hash + std_cmp::Eq + ?Sized,
+        QK : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        let fm = self.conditions.get(cond)?;
+
+        Self::ratio_(fm.get(key), fm.total())
+    }
+
+    /// Obtains the probability of the condition `cond`, i.e. the share of
+    /// the total that was recorded under it, or `None` if the total is not
+    /// positive.
+    pub fn condition_probability<Q>(
+        &self,
+        cond : &Q,
+    ) -> Option<f64>
+    where
+        C : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        Self::ratio_(self.condition_total(cond), self.marginal.total())
+    }
+}
+
+impl<C, K> ConditionalFrequencyMap<C, K> {
+    /// An iterator visiting all conditions in arbitrary order.
+    #[inline]
+    pub fn conditions(&self) -> hash_map::Keys<'_, C, FrequencyMap<K>> {
+        self.conditions.keys()
+    }
+
+    /// An iterator visiting all conditions, along with their outcome
+    /// frequencies, in arbitrary order.
+    #[inline]
+    pub fn iter(&self) -> hash_map::Iter<'_, C, FrequencyMap<K>> {
+        self.conditions.iter()
+    }
+
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.is_empty_()
+    }
+
+    /// Obtains the number of conditions.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len_()
+    }
+
+    /// Obtains the outcome frequencies across all conditions.
+    #[inline]
+    pub fn marginal(&self) -> &FrequencyMap<K> {
+        &self.marginal
+    }
+
+    /// Indicates the total frequency count across all conditions.
+    #[inline]
+    pub fn total(&self) -> isize {
+        self.marginal.total()
+    }
+}
+
+// Implementation
+
+impl<C, K> ConditionalFrequencyMap<C, K> {
+    #[inline]
+    fn is_empty_(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    #[inline]
+    fn len_(&self) -> usize {
+        self.conditions.len()
+    }
+
+    fn ratio_(
+        count : isize,
+        total : isize,
+    ) -> Option<f64> {
+        if total > 0 {
+            Some(count as f64 / total as f64)
+        } else {
+            None
+        }
+    }
+}
+
+// Trait implementations
+
+impl<C, K> Default for ConditionalFrequencyMap<C, K> {
+    /// Creates an empty instance.
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, K> FromIterator<(C, K)> for ConditionalFrequencyMap<C, K>
+where
+    C : std_cmp::Eq + std_hash::Hash,
+    K : Clone + std_cmp::Eq + std_hash::Hash,
+{
+    /// Creates an instance from an iterator of condition-outcome pairs.
+    fn from_iter<T : IntoIterator<Item = (C, K)>>(iter : T) -> Self {
+        let mut cfm = Self::new();
+
+        for (cond, key) in iter {
+            cfm.push(cond, key);
+        }
+
+        cfm
+    }
+}
+
+impl<C, K> IsEmpty for ConditionalFrequencyMap<C, K> {
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.is_empty_()
+    }
+}
+
+impl<C, K> Len for ConditionalFrequencyMap<C, K> {
+    /// Obtains the number of conditions.
+    #[inline]
+    fn len(&self) -> usize {
+        self.len_()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::ConditionalFrequencyMap;
+
+
+    fn make_cfm() -> ConditionalFrequencyMap<&'static str, &'static str> {
+        // (previous word, word) bigrams
+        "the cat sat on the mat and the cat ran"
+            .split(' ')
+            .collect::<Vec<_>>()
+            .windows(2)
+            .map(|w| (w[0], w[1]))
+            .collect()
+    }
+
+    #[test]
+    fn TEST_ConditionalFrequencyMap_Default() {
+        let cfm = ConditionalFrequencyMap::<i32, i32>::default();
+
+        assert!(cfm.is_empty());
+        assert_eq!(0, cfm.len());
+        assert_eq!(0, cfm.total());
+        assert_eq!(0, cfm.conditions().count());
+        assert_eq!(None, cfm.probability(&1, &2));
+        assert_eq!(None, cfm.marginal_probability(&2));
+    }
+
+    #[test]
+    fn TEST_ConditionalFrequencyMap_push_AND_get() {
+        let cfm = make_cfm();
+
+        assert_eq!(6, cfm.len());
+        assert_eq!(9, cfm.total());
+
+        assert_eq!(2, cfm.get("the", "cat"));
+        assert_eq!(1, cfm.get("the", "mat"));
+        assert_eq!(0, cfm.get("the", "sat"));
+        assert_eq!(0, cfm.get("dog", "sat"));
+
+        assert_eq!(3, cfm.condition_total("the"));
+        assert_eq!(2, cfm.condition_total("cat"));
+        assert_eq!(0, cfm.condition_total("ran"));
+
+        let mut conditions : Vec<_> = cfm.conditions().copied().collect();
+
+        conditions.sort();
+
+        assert_eq!(vec!["and", "cat", "mat", "on", "sat", "the"], conditions);
+
+        assert_eq!(2, cfm.marginal()["the"]);
+        assert_eq!(2, cfm.marginal()["cat"]);
+        assert_eq!(cfm.total(), cfm.marginal().total());
+    }
+
+    #[test]
+    fn TEST_ConditionalFrequencyMap_PROBABILITIES() {
+        let cfm = make_cfm();
+
+        assert_eq!(Some(2.0 / 3.0), cfm.probability("the", "cat"));
+        assert_eq!(Some(0.0), cfm.probability("the", "sat"));
+        assert_eq!(Some(0.5), cfm.probability("cat", "ran"));
+        assert_eq!(None, cfm.probability("ran", "the"));
+
+        assert_eq!(Some(3.0 / 9.0), cfm.condition_probability("the"));
+        assert_eq!(Some(2.0 / 9.0), cfm.marginal_probability("cat"));
+    }
+
+    #[test]
+    fn TEST_ConditionalFrequencyMap_push_n_REMOVES_EMPTY_CONDITIONS() {
+        let mut cfm = make_cfm();
+
+        cfm.push_n("on", "the", -1);
+
+        assert_eq!(5, cfm.len());
+        assert!(!cfm.contains_condition("on"));
+        assert_eq!(8, cfm.total());
+        assert_eq!(1, cfm.marginal()["the"]);
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
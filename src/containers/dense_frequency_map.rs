@@ -0,0 +1,568 @@
+// containers/dense_frequency_map.rs : `DenseFrequencyMap`, `DenseKey`
+
+use super::FrequencyMap;
+
+use crate::hash::{
+    hash_map,
+    HashMap,
+};
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use base_traits::{
+    IsEmpty,
+    Len,
+};
+
+use core::{
+    cmp as std_cmp,
+    hash as std_hash,
+    ops as std_ops,
+};
+
+
+/// Maps keys of a [`DenseFrequencyMap`] to and from `usize` indices.
+///
+/// The mapping must be a bijection between keys and the indices they
+/// obtain: `from_index(k.to_index())` must obtain (a key equal to) `k`.
+///
+/// # Examples
+///
+/// ```
+/// use collect_rs::containers::{
+///     DenseFrequencyMap,
+///     DenseKey,
+/// };
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// enum State {
+///     Idle,
+///     Running,
+///     Stopped,
+/// }
+///
+/// impl DenseKey for State {
+///     const DEFAULT_CEILING : usize = 3;
+///
+///     fn to_index(&self) -> usize {
+///         *self as usize
+///     }
+///
+///     fn from_index(index : usize) -> Option<Self> {
+///         [State::Idle, State::Running, State::Stopped].get(index).copied()
+///     }
+/// }
+///
+/// let dfm : DenseFrequencyMap<State> = [State::Idle, State::Running, State::Idle].into_iter().collect();
+///
+/// assert_eq!(2, dfm[&State::Idle]);
+/// assert_eq!(0, dfm[&State::Stopped]);
+/// ```
+pub trait DenseKey : Sized {
+    /// The extent of the contiguous storage of an instance created by
+    /// [`DenseFrequencyMap::default()`].
+    const DEFAULT_CEILING : usize = 256;
+
+    /// Obtains the index of the key.
+    fn to_index(&self) -> usize;
+
+    /// Obtains the key with the given index, or `None` if there is none.
+    fn from_index(index : usize) -> Option<Self>;
+}
+
+macro_rules! impl_dense_key_for_unsigned {
+    ($($type_name:ident, $default_ceiling:expr);* $(;)?) => {
+        $(
+            impl DenseKey for $type_name {
+                const DEFAULT_CEILING : usize = $default_ceiling;
+
+                #[inline]
+                fn to_index(&self) -> usize {
+                    usize::try_from(*self).unwrap_or(usize::MAX)
+                }
+
+                #[inline]
+                fn from_index(index : usize) -> Option<Self> {
+                    $type_name::try_from(index).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_dense_key_for_unsigned!(
+    u8, 256;
+    u16, 256;
+    u32, 256;
+    u64, 256;
+    usize, 256;
+);
+
+impl DenseKey for bool {
+    const DEFAULT_CEILING : usize = 2;
+
+    #[inline]
+    fn to_index(&self) -> usize {
+        *self as usize
+    }
+
+    #[inline]
+    fn from_index(index : usize) -> Option<Self> {
+        match index {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl DenseKey for char {
+    const DEFAULT_CEILING : usize = 128;
+
+    #[inline]
+    fn to_index(&self) -> usize {
+        *self as usize
+    }
+
+    #[inline]
+    fn from_index(index : usize) -> Option<Self> {
+        u32::try_from(index).ok().and_then(char::from_u32)
+    }
+}
+
+
+/// A container that measures the frequencies of the unique keys it
+/// contains, where keys whose [`DenseKey`] index is below a given ceiling
+/// are counted in contiguous storage, and all others in a hash map.
+///
+/// This generalises the approach of
+/// [`UnicodePointMap`](super::UnicodePointMap) to any [`DenseKey`] type,
+/// such as `u8`, `u16`, and small enumerations.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct DenseFrequencyMap<K> {
+    /// Contiguous storage for keys whose index is below the ceiling.
+    vec :   Vec<isize>,
+    /// Map for other keys outside the contiguous range provided by
+    /// `self.vec`.
+    map :   HashMap<K, isize>,
+    /// The number of unique keys represented.
+    len :   usize,
+    /// The total number of keys represented.
+    total : isize,
+}
+
+// API functions
+
+impl<K> DenseFrequencyMap<K> {
+    /// Creates a new instance wherein the contiguous storage portion has
+    /// the extent `contiguous_ceiling`.
+    pub fn new(contiguous_ceiling : usize) -> Self {
+        Self {
+            vec :   vec![0; contiguous_ceiling],
+            map :   HashMap::new(),
+            len :   0,
+            total : 0,
+        }
+    }
+}
+
+// Mutating methods
+
+impl<K> DenseFrequencyMap<K> {
+    /// Clears the map, removing all records and resets `#total()`. Keeps
+    /// the contiguous storage.
+    pub fn clear(&mut self) {
+        self.vec.fill(0);
+        self.map.clear();
+        self.len = 0;
+        self.total = 0;
+    }
+}
+
+impl<K : DenseKey + std_cmp::Eq + std_hash::Hash> DenseFrequencyMap<K> {
+    /// Inserts a record with the given `key` and `count`, replacing any
+    /// existing record with that `key`. In the case that `count` is 0 any
+    /// existing record is removed and no record is created.
+    pub fn insert(
+        &mut self,
+        key : K,
+        count : isize,
+    ) -> Option<isize> {
+        let prev = self.remove(&key);
+
+        self.push_n(key, count);
+
+        prev
+    }
+
+    /// Updates the count by 1 of an existing record identified by `key`, or
+    /// creates, with a count of 1, a new record.
+    #[inline]
+    pub fn push(
+        &mut self,
+        key : K,
+    ) {
+        self.push_n(key, 1);
+    }
+
+    /// Updates the count by `count` of an existing record identifed by
+    /// `key`, or creates, with the given `count`, a new record.
+    ///
+    /// In the case that the resulting count of an existing record is 0 then
+    /// the record is removed.
+    pub fn push_n(
+        &mut self,
+        key : K,
+        count : isize,
+    ) {
+        if 0 == count {
+            return;
+        }
+
+        self.total += count;
+
+        let (prev, curr) = match self.vec.get_mut(key.to_index()) {
+            Some(v) => {
+                let prev = *v;
+
+                *v += count;
+
+                (prev, *v)
+            },
+            None => {
+                match self.map.entry(key) {
+                    hash_map::Entry::Occupied(mut e) => {
+                        let prev = *e.get();
+
+                        *e.get_mut() += count;
+
+                        if 0 == *e.get() {
+                            e.remove();
+                        }
+
+                        (prev, prev + count)
+                    },
+                    hash_map::Entry::Vacant(e) => {
+                        e.insert(count);
+
+                        (0, count)
+                    },
+                }
+            },
+        };
+
+        if 0 == prev {
+            self.len += 1;
+        } else if 0 == curr {
+            self.len -= 1;
+        }
+    }
+
+    /// Removes a key from the map, returning the count of the key if the
+    /// key was previously in the map.
+    pub fn remove(
+        &mut self,
+        key : &K,
+    ) -> Option<isize> {
+        let r = match self.vec.get_mut(key.to_index()) {
+            Some(v) => Some(core::mem::take(v)).filter(|&count| 0 != count),
+            None => self.map.remove(key),
+        };
+
+        if let Some(count) = r {
+            self.len -= 1;
+            self.total -= count;
+        }
+
+        r
+    }
+}
+
+// Non-mutating methods
+
+impl<K : DenseKey + std_cmp::Eq + std_hash::Hash> DenseFrequencyMap<K> {
+    /// Indicates whether a record exists for the given `key`.
+    #[inline]
+    pub fn contains_key(
+        &self,
+        key : &K,
+    ) -> bool {
+        0 != *self.get_(key)
+    }
+
+    /// Obtains the count corresponding to the given `key`, obtaining 0 in
+    /// the case that no such record exists.
+    #[inline]
+    pub fn get(
+        &self,
+        key : &K,
+    ) -> isize {
+        *self.get_(key)
+    }
+}
+
+impl<K> DenseFrequencyMap<K> {
+    /// The extent of the contiguous storage.
+    #[inline]
+    pub fn contiguous_ceiling(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.is_empty_()
+    }
+
+    /// Obtains the number of records.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len_()
+    }
+
+    /// Indicates the total frequency count across all records.
+    #[inline]
+    pub fn total(&self) -> isize {
+        self.total
+    }
+}
+
+impl<K : DenseKey + Clone> DenseFrequencyMap<K> {
+    /// An iterator visiting all key-count pairs, those in contiguous
+    /// storage in index order followed by the others in arbitrary order.
+    /// The iterator element type is `(K, isize)`.
+    pub fn iter(&self) -> DenseFrequencyMapIter<'_, K> {
+        DenseFrequencyMapIter {
+            vec_iter : self.vec.iter().enumerate(),
+            map_iter : self.map.iter(),
+        }
+    }
+}
+
+// Iteration
+
+/// An iterator over the records of a [`DenseFrequencyMap`], obtained from
+/// [`DenseFrequencyMap::iter()`].
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct DenseFrequencyMapIter<'a, K> {
+    /// Iterator over the contiguous storage.
+    vec_iter : core::iter::Enumerate<core::slice::Iter<'a, isize>>,
+    /// Iterator over the map.
+    map_iter : hash_map::Iter<'a, K, isize>,
+}
+
+impl<K : DenseKey + Clone> Iterator for DenseFrequencyMapIter<'_, K> {
+    type Item = (K, isize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (ix, &count) in self.vec_iter.by_ref() {
+            if 0 != count {
+                if let Some(key) = K::from_index(ix) {
+                    return Some((key, count));
+                }
+            }
+        }
+
+        self.map_iter.next().map(|(key, &count)| (key.clone(), count))
+    }
+}
+
+// Implementation
+
+impl<K> DenseFrequencyMap<K> {
+    #[inline]
+    fn is_empty_(&self) -> bool {
+        0 == self.len
+    }
+
+    #[inline]
+    fn len_(&self) -> usize {
+        self.len
+    }
+}
+
+impl<K : DenseKey + std_cmp::Eq + std_hash::Hash> DenseFrequencyMap<K> {
+    fn get_(
+        &self,
+        key : &K,
+    ) -> &isize {
+        match self.vec.get(key.to_index()) {
+            Some(count) => count,
+            None => self.map.get(key).unwrap_or(&0),
+        }
+    }
+}
+
+// Trait implementations
+
+impl<K : DenseKey> Default for DenseFrequencyMap<K> {
+    /// Creates an empty instance whose contiguous storage has the extent
+    /// [`DenseKey::DEFAULT_CEILING`].
+    #[inline]
+    fn default() -> Self {
+        Self::new(K::DEFAULT_CEILING)
+    }
+}
+
+impl<K> From<DenseFrequencyMap<K>> for FrequencyMap<K>
+where
+    K : DenseKey + Clone + std_cmp::Eq + std_hash::Hash,
+{
+    /// Creates a [`FrequencyMap`] from the records of the given instance.
+    fn from(value : DenseFrequencyMap<K>) -> Self {
+        let mut fm = FrequencyMap::with_capacity(value.len());
+
+        for (key, count) in value.iter() {
+            fm.insert(key, count);
+        }
+
+        fm
+    }
+}
+
+impl<K : DenseKey + std_cmp::Eq + std_hash::Hash> FromIterator<K> for DenseFrequencyMap<K> {
+    /// Creates an instance from an iterator of keys.
+    fn from_iter<T : IntoIterator<Item = K>>(iter : T) -> Self {
+        let mut dfm = Self::default();
+
+        for key in iter {
+            dfm.push(key);
+        }
+
+        dfm
+    }
+}
+
+impl<K : DenseKey + std_cmp::Eq + std_hash::Hash> std_ops::Index<&K> for DenseFrequencyMap<K> {
+    type Output = isize;
+
+    #[inline]
+    fn index(
+        &self,
+        key : &K,
+    ) -> &Self::Output {
+        self.get_(key)
+    }
+}
+
+impl<K> IsEmpty for DenseFrequencyMap<K> {
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.is_empty_()
+    }
+}
+
+impl<K> Len for DenseFrequencyMap<K> {
+    /// Obtains the number of records.
+    #[inline]
+    fn len(&self) -> usize {
+        self.len_()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::{
+        DenseFrequencyMap,
+        DenseKey,
+    };
+
+    use crate::containers::FrequencyMap;
+
+
+    fn sorted<K : DenseKey + Clone + Ord>(dfm : &DenseFrequencyMap<K>) -> Vec<(K, isize)> {
+        let mut v : Vec<_> = dfm.iter().collect();
+
+        v.sort();
+
+        v
+    }
+
+    #[test]
+    fn TEST_DenseKey_ROUND_TRIP() {
+        for k in [0u8, 1, 127, 255] {
+            assert_eq!(Some(k), u8::from_index(k.to_index()));
+        }
+
+        assert_eq!(None, u8::from_index(256));
+        assert_eq!(Some(true), bool::from_index(true.to_index()));
+        assert_eq!(None, bool::from_index(2));
+        assert_eq!(Some('🐻'), char::from_index('🐻'.to_index()));
+        assert_eq!(None, char::from_index(0xD800));
+    }
+
+    #[test]
+    fn TEST_DenseFrequencyMap_Default() {
+        let dfm = DenseFrequencyMap::<u16>::default();
+
+        assert!(dfm.is_empty());
+        assert_eq!(0, dfm.len());
+        assert_eq!(0, dfm.total());
+        assert_eq!(256, dfm.contiguous_ceiling());
+        assert_eq!(0, dfm[&80]);
+        assert_eq!(0, dfm.iter().count());
+    }
+
+    #[test]
+    fn TEST_DenseFrequencyMap_push_AND_remove() {
+        let mut dfm = DenseFrequencyMap::<u16>::new(1024);
+
+        for port in [80, 443, 80, 8080, 22, 8080, 80] {
+            dfm.push(port);
+        }
+
+        assert_eq!(4, dfm.len());
+        assert_eq!(7, dfm.total());
+        assert_eq!(3, dfm[&80]);
+        assert_eq!(2, dfm.get(&8080));
+        assert!(dfm.contains_key(&22));
+        assert!(!dfm.contains_key(&21));
+        assert_eq!(vec![(22, 1), (80, 3), (443, 1), (8080, 2)], sorted(&dfm));
+
+        dfm.push_n(80, -3);
+        dfm.push_n(8080, -2);
+
+        assert_eq!(2, dfm.len());
+        assert_eq!(2, dfm.total());
+
+        assert_eq!(Some(1), dfm.insert(443, 5));
+        assert_eq!(None, dfm.insert(9000, 2));
+        assert_eq!(Some(1), dfm.remove(&22));
+        assert_eq!(None, dfm.remove(&22));
+        assert_eq!(Some(2), dfm.remove(&9000));
+
+        assert_eq!(vec![(443, 5)], sorted(&dfm));
+        assert_eq!(1, dfm.len());
+        assert_eq!(5, dfm.total());
+
+        dfm.clear();
+
+        assert!(dfm.is_empty());
+        assert_eq!(0, dfm.total());
+        assert_eq!(1024, dfm.contiguous_ceiling());
+    }
+
+    #[test]
+    fn TEST_DenseFrequencyMap_INTO_FrequencyMap() {
+        let dfm : DenseFrequencyMap<u8> = b"abracadabra".iter().copied().collect();
+
+        let fm = FrequencyMap::from(dfm);
+
+        assert_eq!(5, fm.len());
+        assert_eq!(11, fm.total());
+        assert_eq!(5, fm[&b'a']);
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
@@ -0,0 +1,559 @@
+// containers/frequency_delta.rs : `FrequencyDelta`
+
+use super::{
+    FrequencyMap,
+    UnicodePointMap,
+};
+
+use base_traits::{
+    IsEmpty,
+    Len,
+};
+
+use alloc::vec::Vec;
+
+use core::{
+    cmp as std_cmp,
+    hash as std_hash,
+};
+
+
+type Records<K> = Vec<(K, isize)>;
+type ChangedRecords<K> = Vec<(K, isize, isize)>;
+
+
+/// The difference between two snapshots of a [`FrequencyMap`] or
+/// [`UnicodePointMap`], as obtained from `diff()`.
+///
+/// A delta obtained from `a.diff(&b)` records how to get from `a` to `b`:
+/// applying it to `a` (via `apply()`) yields `b`, and reverting it from `b`
+/// (via `revert()`) yields `a`. Because it is applied as per-key count
+/// deltas, a delta may also be applied to a map other than the one from
+/// which it was obtained.
+///
+/// A delta may be shipped between processes, e.g. as incremental updates
+/// between nodes, by sending its parts, as obtained from
+/// [`#into_parts()`](Self::into_parts), and rebuilding it on receipt via
+/// [`FrequencyDelta::from_parts()`].
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub struct FrequencyDelta<K> {
+    /// Records present only in the newer snapshot, with their counts.
+    added :   Vec<(K, isize)>,
+    /// Records present only in the older snapshot, with their counts.
+    removed : Vec<(K, isize)>,
+    /// Records present in both snapshots with differing counts, as
+    /// `(key, old_count, new_count)`.
+    changed : Vec<(K, isize, isize)>,
+}
+
+// API functions
+
+impl<K> FrequencyDelta<K> {
+    /// Creates an empty instance, which represents no change.
+    pub fn new() -> Self {
+        Self {
+            added :   Vec::new(),
+            removed : Vec::new(),
+            changed : Vec::new(),
+        }
+    }
+
+    /// Creates an instance from its parts, as obtained from
+    /// [`#into_parts()`](Self::into_parts), or from
+    /// [`#added()`](Self::added), [`#removed()`](Self::removed), and
+    /// [`#changed()`](Self::changed).
+    pub fn from_parts(
+        added : Vec<(K, isize)>,
+        removed : Vec<(K, isize)>,
+        changed : Vec<(K, isize, isize)>,
+    ) -> Self {
+        Self {
+            added,
+            removed,
+            changed,
+        }
+    }
+}
+
+// Mutating methods
+
+impl<K> FrequencyDelta<K> {
+    /// Consumes the instance, obtaining its parts - the added, removed,
+    /// and changed records - from which it may be recreated by
+    /// [`FrequencyDelta::from_parts()`].
+    pub fn into_parts(self) -> (Records<K>, Records<K>, ChangedRecords<K>) {
+        (self.added, self.removed, self.changed)
+    }
+}
+
+// Non-mutating methods
+
+impl<K> FrequencyDelta<K> {
+    /// Records present only in the newer snapshot, with their counts.
+    #[inline]
+    pub fn added(&self) -> &[(K, isize)] {
+        &self.added
+    }
+
+    /// Records present in both snapshots with differing counts, as
+    /// `(key, old_count, new_count)`.
+    #[inline]
+    pub fn changed(&self) -> &[(K, isize, isize)] {
+        &self.changed
+    }
+
+    /// An iterator visiting each affected key along with its count delta
+    /// (new count minus old count). The iterator element type is
+    /// `(&'a K, i128)`, being wide enough that the delta between any two
+    /// `isize` counts cannot overflow.
+    pub fn deltas(&self) -> impl Iterator<Item = (&K, i128)> + '_ {
+        self.added
+            .iter()
+            .map(|(key, count)| (key, *count as i128))
+            .chain(self.removed.iter().map(|(key, count)| (key, -(*count as i128))))
+            .chain(self.changed.iter().map(|(key, old, new)| (key, *new as i128 - *old as i128)))
+    }
+
+    /// Indicates whether the delta represents no change.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.is_empty_()
+    }
+
+    /// Obtains the number of affected keys.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len_()
+    }
+
+    /// Records present only in the older snapshot, with their counts.
+    #[inline]
+    pub fn removed(&self) -> &[(K, isize)] {
+        &self.removed
+    }
+
+    /// The change in total frequency count between the snapshots.
+    pub fn total_delta(&self) -> i128 {
+        self.deltas().map(|(_, delta)| delta).sum()
+    }
+}
+
+impl<K : Clone> FrequencyDelta<K> {
+    /// Obtains the inverse delta, i.e. that which leads from the newer
+    /// snapshot to the older.
+    pub fn inverse(&self) -> Self {
+        Self {
+            added :   self.removed.clone(),
+            removed : self.added.clone(),
+            changed : self.changed.iter().map(|(key, old, new)| (key.clone(), *new, *old)).collect(),
+        }
+    }
+}
+
+// Implementation
+
+impl<K> FrequencyDelta<K> {
+    #[inline]
+    fn is_empty_(&self) -> bool {
+        self.len_() == 0
+    }
+
+    #[inline]
+    fn len_(&self) -> usize {
+        self.added.len() + self.removed.len() + self.changed.len()
+    }
+
+    fn record_(
+        &mut self,
+        key : K,
+        old : isize,
+        new : isize,
+    ) {
+        match (old, new) {
+            _ if old == new => {},
+            (0, _) => self.added.push((key, new)),
+            (_, 0) => self.removed.push((key, old)),
+            _ => self.changed.push((key, old, new)),
+        }
+    }
+}
+
+/// Obtains the count that results from adding `count_delta` to `count`.
+///
+/// # Panics
+///
+/// Panics if the result is not representable as `isize`, which cannot
+/// happen when a delta is applied to (or reverted from) the snapshot from
+/// which it was obtained.
+fn adjust_count_(
+    count : isize,
+    count_delta : i128,
+) -> isize {
+    isize::try_from(count as i128 + count_delta).expect("count out of range of `isize`")
+}
+
+// Trait implementations
+
+impl<K> Default for FrequencyDelta<K> {
+    /// Creates an empty instance, which represents no change.
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> IsEmpty for FrequencyDelta<K> {
+    /// Indicates whether the delta represents no change.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.is_empty_()
+    }
+}
+
+impl<K> Len for FrequencyDelta<K> {
+    /// Obtains the number of affected keys.
+    #[inline]
+    fn len(&self) -> usize {
+        self.len_()
+    }
+}
+
+
+// FrequencyMap
+
+impl<K : Clone + std_cmp::Eq + std_hash::Hash> FrequencyMap<K> {
+    /// Applies the given `delta`, adding each of its per-key count deltas
+    /// to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a resulting count is not representable as `isize`.
+    pub fn apply(
+        &mut self,
+        delta : &FrequencyDelta<K>,
+    ) {
+        for (key, count_delta) in delta.deltas() {
+            let count = adjust_count_(self.get(key), count_delta);
+
+            self.insert(key.clone(), count);
+        }
+    }
+
+    /// Obtains the delta that leads from `self` to `other`.
+    pub fn diff(
+        &self,
+        other : &Self,
+    ) -> FrequencyDelta<K> {
+        let mut delta = FrequencyDelta::new();
+
+        for (key, &old) in self.iter() {
+            delta.record_(key.clone(), old, other.get(key));
+        }
+
+        for (key, &new) in other.iter() {
+            if !self.contains_key(key) {
+                delta.record_(key.clone(), 0, new);
+            }
+        }
+
+        delta
+    }
+
+    /// Reverts the given `delta`, subtracting each of its per-key count
+    /// deltas from `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a resulting count is not representable as `isize`.
+    pub fn revert(
+        &mut self,
+        delta : &FrequencyDelta<K>,
+    ) {
+        for (key, count_delta) in delta.deltas() {
+            let count = adjust_count_(self.get(key), -count_delta);
+
+            self.insert(key.clone(), count);
+        }
+    }
+}
+
+
+// UnicodePointMap
+
+impl UnicodePointMap {
+    /// Applies the given `delta`, adding each of its per-key count deltas
+    /// to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a resulting count is not representable as `isize`.
+    pub fn apply(
+        &mut self,
+        delta : &FrequencyDelta<char>,
+    ) {
+        for (&c, count_delta) in delta.deltas() {
+            let count = adjust_count_(self.get(&c), count_delta);
+
+            self.insert(c, count);
+        }
+    }
+
+    /// Obtains the delta that leads from `self` to `other`.
+    pub fn diff(
+        &self,
+        other : &Self,
+    ) -> FrequencyDelta<char> {
+        let mut delta = FrequencyDelta::new();
+
+        for (c, old) in self.iter() {
+            delta.record_(c, old, other.get(&c));
+        }
+
+        for (c, new) in other.iter() {
+            if !self.contains_key(&c) {
+                delta.record_(c, 0, new);
+            }
+        }
+
+        delta
+    }
+
+    /// Reverts the given `delta`, subtracting each of its per-key count
+    /// deltas from `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a resulting count is not representable as `isize`.
+    pub fn revert(
+        &mut self,
+        delta : &FrequencyDelta<char>,
+    ) {
+        for (&c, count_delta) in delta.deltas() {
+            let count = adjust_count_(self.get(&c), -count_delta);
+
+            self.insert(c, count);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::FrequencyDelta;
+
+    use crate::containers::{
+        FrequencyMap,
+        UnicodePointMap,
+    };
+
+
+    fn sorted<T : Clone + Ord>(v : &[T]) -> Vec<T> {
+        let mut v = v.to_vec();
+
+        v.sort();
+
+        v
+    }
+
+    #[test]
+    fn TEST_FrequencyDelta_Default() {
+        let delta = FrequencyDelta::<i32>::default();
+
+        assert!(delta.is_empty());
+        assert_eq!(0, delta.len());
+        assert_eq!(0, delta.total_delta());
+        assert_eq!(delta, delta.inverse());
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_diff_1() {
+        let a = FrequencyMap::<&str>::from([
+            // insert list
+            ("cat", 2),
+            ("dog", 3),
+            ("eel", 1),
+        ]);
+        let b = FrequencyMap::<&str>::from([
+            // insert list
+            ("cat", 2),
+            ("dog", 5),
+            ("fox", 4),
+        ]);
+
+        let delta = a.diff(&b);
+
+        assert!(!delta.is_empty());
+        assert_eq!(3, delta.len());
+        assert_eq!(&[("fox", 4)], delta.added());
+        assert_eq!(&[("eel", 1)], delta.removed());
+        assert_eq!(&[("dog", 3, 5)], delta.changed());
+        assert_eq!(5, delta.total_delta());
+        assert_eq!(vec![(&"dog", 2), (&"eel", -1), (&"fox", 4)], sorted(&delta.deltas().collect::<Vec<_>>()));
+
+        assert!(a.diff(&a).is_empty());
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_apply_AND_revert() {
+        let a = FrequencyMap::<i32>::from([
+            // insert list
+            (1, 2),
+            (2, 3),
+            (3, -1),
+        ]);
+        let b = FrequencyMap::<i32>::from([
+            // insert list
+            (2, 1),
+            (3, 4),
+            (4, 7),
+        ]);
+
+        let delta = a.diff(&b);
+
+        let mut fm = a.clone();
+
+        fm.apply(&delta);
+
+        assert!(fm.diff(&b).is_empty());
+        assert_eq!(b.len(), fm.len());
+        assert_eq!(b.total(), fm.total());
+
+        fm.revert(&delta);
+
+        assert!(fm.diff(&a).is_empty());
+        assert_eq!(a.len(), fm.len());
+        assert_eq!(a.total(), fm.total());
+
+        let mut fm = b.clone();
+
+        fm.apply(&delta.inverse());
+
+        assert!(fm.diff(&a).is_empty());
+        assert_eq!(a.total(), fm.total());
+    }
+
+    #[test]
+    fn TEST_FrequencyDelta_from_parts_ROUND_TRIP() {
+        let a = FrequencyMap::<String>::from([
+            // insert list
+            ("cat".to_string(), 2),
+            ("dog".to_string(), 3),
+        ]);
+        let b = FrequencyMap::<String>::from([
+            // insert list
+            ("dog".to_string(), 1),
+            ("eel".to_string(), 4),
+        ]);
+
+        let delta = a.diff(&b);
+
+        // as if sent from one node to another
+        let (added, removed, changed) = delta.clone().into_parts();
+
+        let received = FrequencyDelta::from_parts(added, removed, changed);
+
+        assert_eq!(delta, received);
+
+        let mut fm = a.clone();
+
+        fm.apply(&received);
+
+        assert!(fm.diff(&b).is_empty());
+        assert_eq!(b.total(), fm.total());
+
+        let received = FrequencyDelta::from_parts(delta.added().to_vec(), delta.removed().to_vec(), delta.changed().to_vec());
+
+        fm.revert(&received);
+
+        assert!(fm.diff(&a).is_empty());
+        assert_eq!(a.total(), fm.total());
+    }
+
+    #[test]
+    fn TEST_FrequencyDelta_EXTREME_COUNTS() {
+        let delta = FrequencyDelta::from_parts(vec![('w', isize::MIN)], vec![('y', isize::MIN)], vec![('x', isize::MIN, isize::MAX)]);
+
+        assert_eq!(
+            vec![
+                (&'w', isize::MIN as i128),
+                (&'x', isize::MAX as i128 - isize::MIN as i128),
+                (&'y', -(isize::MIN as i128)),
+            ],
+            sorted(&delta.deltas().collect::<Vec<_>>())
+        );
+        assert_eq!(isize::MAX as i128 - isize::MIN as i128, delta.total_delta());
+        assert_eq!(isize::MIN as i128 - isize::MAX as i128, delta.inverse().total_delta());
+
+        let a = FrequencyMap::<char>::from([
+            // insert list
+            ('x', isize::MIN),
+        ]);
+        let b = FrequencyMap::<char>::from([
+            // insert list
+            ('x', isize::MAX),
+        ]);
+
+        let delta = a.diff(&b);
+
+        let mut fm = a.clone();
+
+        fm.apply(&delta);
+
+        assert_eq!(isize::MAX, fm[&'x']);
+
+        fm.revert(&delta);
+
+        assert_eq!(isize::MIN, fm[&'x']);
+
+        let mut upm = UnicodePointMap::from([
+            // insert list
+            ('y', isize::MIN + 1),
+        ]);
+
+        let delta = upm.diff(&UnicodePointMap::default());
+
+        upm.apply(&delta);
+
+        assert!(upm.is_empty());
+
+        upm.revert(&delta);
+
+        assert_eq!(1, upm.len());
+        assert_eq!(isize::MIN + 1, upm['y']);
+    }
+
+    #[test]
+    fn TEST_UnicodePointMap_diff_1() {
+        let a = UnicodePointMap::from_iter("hello".chars());
+        let b = UnicodePointMap::from_iter("help🐻".chars());
+
+        let delta = a.diff(&b);
+
+        assert_eq!(vec![('p', 1), ('🐻', 1)], sorted(delta.added()));
+        assert_eq!(vec![('o', 1)], sorted(delta.removed()));
+        assert_eq!(vec![('l', 2, 1)], sorted(delta.changed()));
+        assert_eq!(0, delta.total_delta());
+
+        let mut upm = UnicodePointMap::from_iter("hello".chars());
+
+        upm.apply(&delta);
+
+        assert!(upm.diff(&b).is_empty());
+        assert_eq!(b.len(), upm.len());
+        assert_eq!(b.total(), upm.total());
+
+        upm.revert(&delta);
+
+        assert!(upm.diff(&a).is_empty());
+        assert_eq!(a.len(), upm.len());
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
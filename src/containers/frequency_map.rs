@@ -1,18 +1,30 @@
 // containers/frequency_map.rs : `FrequencyMap`
 
+use crate::hash::{
+    hash_map,
+    HashMap,
+};
+
 use base_traits::{
     IsEmpty,
     Len,
 };
 
-use std::{
+use alloc::borrow::ToOwned;
+
+use core::{
     borrow as std_borrow,
     cmp as std_cmp,
-    collections::HashMap,
     hash as std_hash,
     ops as std_ops,
 };
 
+#[cfg(not(feature = "std"))]
+use core::hash::BuildHasher as _;
+
+#[cfg(not(feature = "std"))]
+use hashbrown::hash_map::RawEntryMut;
+
 
 /// A container that measures the frequencies of the unique elements it
 /// contains.
@@ -100,7 +112,7 @@ impl<K: std_cmp::Eq + std_hash::Hash> FrequencyMap<K> {
     /// Clears the map, returning all key-count pairs as an iterator. Keeps
     /// the allocated memory for reuse.
     #[inline]
-    pub fn drain(&mut self) -> std::collections::hash_map::Drain<'_, K, isize> {
+    pub fn drain(&mut self) -> hash_map::Drain<'_, K, isize> {
         self.total = 0;
 
         self.map.drain()
@@ -141,6 +153,80 @@ impl<K: std_cmp::Eq + std_hash::Hash> FrequencyMap<K> {
         }
     }
 
+    /// Inserts a record with the given `key` and `count`, as
+    /// [`#insert()`](Self::insert), except that `key` is cloned into an
+    /// owned key only in the case that a new record is created.
+    ///
+    /// With **hashbrown** storage (i.e. without the **std** feature), the
+    /// record is found, or created, with a single lookup of `key`. With
+    /// **std**, creating a new record costs two lookups of `key` - one that
+    /// finds no existing record, and another that inserts the owned key -
+    /// since `std::collections::HashMap` provides no means of inserting a
+    /// key obtained from `ToOwned` in the same lookup, and updating an
+    /// existing record costs one lookup, or two if it is removed.
+    pub fn insert_borrowed<Q>(
+        &mut self,
+        key: &Q,
+        count: isize,
+    ) -> Option<isize>
+    where
+        K: std_borrow::Borrow<Q>,
+        Q: std_hash::Hash + std_cmp::Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        #[cfg(not(feature = "std"))]
+        {
+            let hash = self.map.hasher().hash_one(key);
+
+            match self.map.raw_entry_mut().from_key_hashed_nocheck(hash, key) {
+                RawEntryMut::Occupied(mut e) => {
+                    let r = Some(*e.get());
+
+                    self.total += count - *e.get();
+
+                    if 0 == count {
+                        e.remove();
+                    } else {
+                        *e.get_mut() = count;
+                    }
+
+                    r
+                },
+                RawEntryMut::Vacant(e) => {
+                    if 0 != count {
+                        e.insert_hashed_nocheck(hash, key.to_owned(), count);
+
+                        self.total += count;
+                    }
+
+                    None
+                },
+            }
+        }
+
+        #[cfg(feature = "std")]
+        if let Some(v) = self.map.get_mut(key) {
+            let r = Some(*v);
+
+            self.total += count - *v;
+
+            if 0 == count {
+                self.map.remove(key);
+            } else {
+                *v = count;
+            }
+
+            r
+        } else {
+            if 0 != count {
+                self.map.insert(key.to_owned(), count);
+
+                self.total += count;
+            }
+
+            None
+        }
+    }
+
     // pub fn merge(&mut self, )
 
     /// Updates the count by 1 of an existing record identified by `key`, or
@@ -152,7 +238,18 @@ impl<K: std_cmp::Eq + std_hash::Hash> FrequencyMap<K> {
         &mut self,
         key : K,
     ) {
-        self.map.entry(key).and_modify(|v| *v += 1).or_insert(1);
+        match self.map.entry(key) {
+            hash_map::Entry::Occupied(mut e) => {
+                *e.get_mut() += 1;
+
+                if 0 == *e.get() {
+                    e.remove();
+                }
+            },
+            hash_map::Entry::Vacant(e) => {
+                e.insert(1);
+            },
+        }
 
         self.total += 1;
     }
@@ -174,6 +271,80 @@ impl<K: std_cmp::Eq + std_hash::Hash> FrequencyMap<K> {
         self.total += Self::push_into_map_(&mut self.map, key, count);
     }
 
+    /// Updates the count by 1 of an existing record identified by `key`, or
+    /// creates, with a count of 1, a new record, as
+    /// [`#push()`](Self::push), except that `key` is cloned into an owned
+    /// key only in the case that a new record is created.
+    ///
+    /// The number of lookups of `key` is as described for
+    /// [`#push_n_borrowed()`](Self::push_n_borrowed).
+    #[inline]
+    pub fn push_borrowed<Q>(
+        &mut self,
+        key: &Q,
+    )
+    where
+        K: std_borrow::Borrow<Q>,
+        Q: std_hash::Hash + std_cmp::Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.push_n_borrowed(key, 1);
+    }
+
+    /// Updates the count by `count` of an existing record identifed by
+    /// `key`, or creates, with the given `count`, a new record, as
+    /// [`#push_n()`](Self::push_n), except that `key` is cloned into an
+    /// owned key only in the case that a new record is created.
+    ///
+    /// With **hashbrown** storage (i.e. without the **std** feature), the
+    /// record is found, or created, with a single lookup of `key`. With
+    /// **std**, creating a new record costs two lookups of `key` - one that
+    /// finds no existing record, and another that inserts the owned key -
+    /// since `std::collections::HashMap` provides no means of inserting a
+    /// key obtained from `ToOwned` in the same lookup, and updating an
+    /// existing record costs one lookup, or two if it is removed.
+    pub fn push_n_borrowed<Q>(
+        &mut self,
+        key: &Q,
+        count: isize,
+    )
+    where
+        K: std_borrow::Borrow<Q>,
+        Q: std_hash::Hash + std_cmp::Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if 0 != count {
+            #[cfg(not(feature = "std"))]
+            {
+                let hash = self.map.hasher().hash_one(key);
+
+                match self.map.raw_entry_mut().from_key_hashed_nocheck(hash, key) {
+                    RawEntryMut::Occupied(mut e) => {
+                        *e.get_mut() += count;
+
+                        if 0 == *e.get() {
+                            e.remove();
+                        }
+                    },
+                    RawEntryMut::Vacant(e) => {
+                        e.insert_hashed_nocheck(hash, key.to_owned(), count);
+                    },
+                }
+            }
+
+            #[cfg(feature = "std")]
+            if let Some(v) = self.map.get_mut(key) {
+                *v += count;
+
+                if 0 == *v {
+                    self.map.remove(key);
+                }
+            } else {
+                self.map.insert(key.to_owned(), count);
+            }
+
+            self.total += count;
+        }
+    }
+
     /// Removes a key from the map, returning the count of the key if the
     /// key was previously in the map.
     #[inline]
@@ -244,10 +415,10 @@ impl<K> FrequencyMap<K> {
     #[inline]
     pub fn retain<F>(
         &mut self,
-        f: F,
+        mut f: F,
     )
     where
-        F: Fn(&K, isize) -> bool,
+        F: FnMut(&K, isize) -> bool,
     {
         let mut dropped_total = 0;
 
@@ -265,6 +436,54 @@ impl<K> FrequencyMap<K> {
     }
 }
 
+impl<K> FrequencyMap<K> {
+    /// Consumes the instance, obtaining a new instance whose keys are those
+    /// obtained by applying `f` to each key, or omitting the record where
+    /// `f` returns `None`.
+    ///
+    /// The counts of records whose keys collide after mapping are summed,
+    /// and a record whose sum is 0 is omitted.
+    pub fn filter_map_keys<K2, F>(
+        self,
+        mut f: F,
+    ) -> FrequencyMap<K2>
+    where
+        K2: std_cmp::Eq + std_hash::Hash,
+        F: FnMut(K) -> Option<K2>,
+    {
+        let mut map = HashMap::with_capacity(self.map.len());
+        let mut total = 0;
+
+        for (key, count) in self.map {
+            if let Some(key) = f(key) {
+                total += FrequencyMap::push_into_map_(&mut map, key, count);
+            }
+        }
+
+        FrequencyMap {
+            map,
+            total,
+        }
+    }
+
+    /// Consumes the instance, obtaining a new instance whose keys are those
+    /// obtained by applying `f` to each key.
+    ///
+    /// The counts of records whose keys collide after mapping are summed,
+    /// and a record whose sum is 0 is omitted.
+    #[inline]
+    pub fn map_keys<K2, F>(
+        self,
+        mut f: F,
+    ) -> FrequencyMap<K2>
+    where
+        K2: std_cmp::Eq + std_hash::Hash,
+        F: FnMut(K) -> K2,
+    {
+        self.filter_map_keys(|key| Some(f(key)))
+    }
+}
+
 // Non-mutating methods
 
 impl<K : std_cmp::Eq + std_hash::Hash> FrequencyMap<K> {
@@ -300,7 +519,7 @@ impl<K> FrequencyMap<K> {
     /// An iterator visiting all key-count pairs in arbitrary order. The
     /// iterator element type is `(&'a K, &'a isize)`.
     #[inline]
-    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, K, isize> {
+    pub fn iter(&self) -> hash_map::Iter<'_, K, isize> {
         self.map.iter()
     }
 }
@@ -351,6 +570,17 @@ impl<K : std_cmp::Eq + std_hash::Hash> FrequencyMap<K> {
         }
     }
 
+    pub(crate) fn get_key_value_<Q>(
+        &self,
+        key: &Q,
+    ) -> Option<(&K, isize)>
+    where
+        K: std_borrow::Borrow<Q>,
+        Q: std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        self.map.get_key_value(key).map(|(k, &count)| (k, count))
+    }
+
     fn push_into_map_(
         map : &mut HashMap<K, isize>,
         key : K,
@@ -379,7 +609,7 @@ impl<K : std_cmp::Eq + std_hash::Hash> FrequencyMap<K> {
 impl<K> FrequencyMap<K> {
     #[inline]
     fn is_empty_(&self) -> bool {
-        0 == self.total
+        0 == self.len_()
     }
 
     #[inline]
@@ -1449,6 +1679,230 @@ mod tests {
         assert_eq!(0, fm.get(&102));
     }
 
+    #[test]
+    fn TEST_FrequencyMap_push_borrowed_1() {
+        let mut fm = FrequencyMap::<String>::new();
+
+        for word in "the cat and the hat and the bat".split(' ') {
+            fm.push_borrowed(word);
+        }
+
+        assert_eq!(5, fm.len());
+        assert_eq!(8, fm.total());
+        assert_eq!(3, fm["the"]);
+        assert_eq!(2, fm["and"]);
+
+        fm.push_n_borrowed("the", -3);
+        fm.push_n_borrowed("cat", 0);
+        fm.push_n_borrowed("fox", 4);
+
+        assert_eq!(5, fm.len());
+        assert_eq!(9, fm.total());
+        assert!(!fm.contains_key("the"));
+        assert_eq!(1, fm["cat"]);
+        assert_eq!(4, fm["fox"]);
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_insert_borrowed_1() {
+        let mut fm = FrequencyMap::<String>::new();
+
+        assert_eq!(None, fm.insert_borrowed("cat", 3));
+        assert_eq!(None, fm.insert_borrowed("dog", 0));
+        assert_eq!(Some(3), fm.insert_borrowed("cat", 5));
+
+        assert_eq!(1, fm.len());
+        assert_eq!(5, fm.total());
+
+        assert_eq!(Some(5), fm.insert_borrowed("cat", 0));
+
+        assert!(fm.is_empty());
+        assert_eq!(0, fm.len());
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_borrowed_CALLS_to_owned_ONLY_ON_MISS() {
+        use std::{
+            borrow::Borrow,
+            cell::Cell,
+        };
+
+        thread_local! {
+            static TO_OWNED_CALLS : Cell<usize> = const { Cell::new(0) };
+        }
+
+        // a borrowed key that is not `Clone`, so that its `ToOwned`
+        // implementation may count its calls
+        #[derive(Debug)]
+        #[derive(PartialEq)]
+        #[derive(Eq)]
+        #[derive(Hash)]
+        struct Probe(u32);
+
+        #[derive(Debug)]
+        #[derive(PartialEq)]
+        #[derive(Eq)]
+        #[derive(Hash)]
+        struct OwnedProbe(Probe);
+
+        impl Borrow<Probe> for OwnedProbe {
+            fn borrow(&self) -> &Probe {
+                &self.0
+            }
+        }
+
+        impl ToOwned for Probe {
+            type Owned = OwnedProbe;
+
+            fn to_owned(&self) -> OwnedProbe {
+                TO_OWNED_CALLS.with(|calls| calls.set(calls.get() + 1));
+
+                OwnedProbe(Probe(self.0))
+            }
+        }
+
+        let mut fm = FrequencyMap::<OwnedProbe>::new();
+
+        fm.push_borrowed(&Probe(1));
+        fm.push_borrowed(&Probe(1));
+        fm.push_borrowed(&Probe(1));
+
+        assert_eq!(1, TO_OWNED_CALLS.with(Cell::get));
+
+        fm.push_n_borrowed(&Probe(2), 5);
+        fm.push_n_borrowed(&Probe(2), 2);
+        fm.push_n_borrowed(&Probe(3), 0);
+
+        assert_eq!(2, TO_OWNED_CALLS.with(Cell::get));
+
+        assert_eq!(Some(3), fm.insert_borrowed(&Probe(1), 7));
+        assert_eq!(None, fm.insert_borrowed(&Probe(4), 0));
+        assert_eq!(None, fm.insert_borrowed(&Probe(5), 1));
+
+        assert_eq!(3, TO_OWNED_CALLS.with(Cell::get));
+
+        assert_eq!(3, fm.len());
+        assert_eq!(15, fm.total());
+        assert_eq!(7, fm[&Probe(1)]);
+        assert_eq!(7, fm[&Probe(2)]);
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[test]
+    fn TEST_FrequencyMap_borrowed_HASHES_KEY_ONCE() {
+        use std::{
+            borrow::Borrow,
+            cell::Cell,
+            hash::{
+                Hash,
+                Hasher,
+            },
+        };
+
+        thread_local! {
+            static HASH_CALLS : Cell<usize> = const { Cell::new(0) };
+        }
+
+        // a borrowed key whose `Hash` implementation counts its calls, each
+        // of which corresponds to a lookup
+        #[derive(Debug)]
+        #[derive(PartialEq)]
+        #[derive(Eq)]
+        struct Probe(u32);
+
+        impl Hash for Probe {
+            fn hash<H : Hasher>(
+                &self,
+                state : &mut H,
+            ) {
+                HASH_CALLS.with(|calls| calls.set(calls.get() + 1));
+
+                self.0.hash(state);
+            }
+        }
+
+        #[derive(Debug)]
+        #[derive(PartialEq)]
+        #[derive(Eq)]
+        #[derive(Hash)]
+        struct OwnedProbe(Probe);
+
+        impl Borrow<Probe> for OwnedProbe {
+            fn borrow(&self) -> &Probe {
+                &self.0
+            }
+        }
+
+        impl ToOwned for Probe {
+            type Owned = OwnedProbe;
+
+            fn to_owned(&self) -> OwnedProbe {
+                OwnedProbe(Probe(self.0))
+            }
+        }
+
+        let hash_calls = || HASH_CALLS.with(Cell::get);
+
+        // sufficient capacity that no rehashing occurs
+        let mut fm = FrequencyMap::<OwnedProbe>::with_capacity(16);
+
+        fm.push_borrowed(&Probe(1));
+
+        assert_eq!(1, hash_calls());
+
+        fm.push_n_borrowed(&Probe(1), 2);
+        fm.push_n_borrowed(&Probe(2), 5);
+        fm.push_n_borrowed(&Probe(2), -5);
+
+        assert_eq!(4, hash_calls());
+
+        assert_eq!(Some(3), fm.insert_borrowed(&Probe(1), 0));
+        assert_eq!(None, fm.insert_borrowed(&Probe(3), 4));
+
+        assert_eq!(6, hash_calls());
+        assert_eq!(1, fm.len());
+        assert_eq!(4, fm.total());
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_map_keys_1() {
+        let fm = FrequencyMap::<&str>::from([
+            // insert list
+            ("Cat", 2),
+            ("cat", 3),
+            ("CAT", 1),
+            ("Dog", 4),
+            ("dog", -4),
+            ("eel", 1),
+        ]);
+
+        let fm = fm.map_keys(|key| key.to_lowercase());
+
+        assert_eq!(2, fm.len());
+        assert_eq!(7, fm.total());
+        assert_eq!(6, fm["cat"]);
+        assert!(!fm.contains_key("dog"));
+        assert_eq!(1, fm["eel"]);
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_filter_map_keys_1() {
+        let fm = FrequencyMap::<&str>::from([
+            // insert list
+            ("1", 2),
+            ("01", 3),
+            ("two", 5),
+            ("12", 1),
+        ]);
+
+        let fm = fm.filter_map_keys(|key| key.parse::<i32>().ok().map(|n| n / 10));
+
+        assert_eq!(2, fm.len());
+        assert_eq!(6, fm.total());
+        assert_eq!(5, fm[&0]);
+        assert_eq!(1, fm[&1]);
+    }
+
     #[test]
     fn TEST_FrequencyMap_EXAMPLE_1() {
 
@@ -1462,6 +1916,56 @@ mod tests {
         assert_eq!(2, fm.get("dog"));
         assert_eq!(0, fm.get("mouse"));
     }
+
+    #[test]
+    fn TEST_FrequencyMap_validate_DETECTS_CORRUPTION() {
+        use crate::containers::InvariantError;
+
+        let mut fm = FrequencyMap::from([("cat", 1), ("dog", 2)]);
+
+        assert_eq!(Ok(()), fm.validate());
+
+        fm.total = 4;
+
+        assert_eq!(Err(InvariantError::TotalMismatch { stored : 4, computed : 3 }), fm.validate());
+
+        fm.total = 3;
+        fm.map.insert("mouse", 0);
+
+        assert_eq!(Err(InvariantError::ZeroCountRecord { code_point : None }), fm.validate());
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_push_REMOVES_ZERO_COUNT_RECORD() {
+        let mut fm = FrequencyMap::from([("cat", -1), ("dog", -2)]);
+
+        fm.push("cat");
+        fm.push("dog");
+
+        assert!(!fm.contains_key("cat"));
+        assert_eq!(-1, fm.get("dog"));
+        assert_eq!(1, fm.len());
+        assert_eq!(-1, fm.total());
+        assert_eq!(Ok(()), fm.validate());
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_is_empty_WITH_ZERO_TOTAL() {
+        let mut fm = FrequencyMap::new();
+
+        fm.push("a");
+        fm.push_n("b", -1);
+
+        assert_eq!(0, fm.total());
+        assert_eq!(2, fm.len());
+        assert!(!fm.is_empty());
+        assert!(!base_traits::IsEmpty::is_empty(&fm));
+
+        fm.remove("a");
+        fm.remove("b");
+
+        assert!(fm.is_empty());
+    }
 }
 
 
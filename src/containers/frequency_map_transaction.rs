@@ -0,0 +1,259 @@
+// containers/frequency_map_transaction.rs : `FrequencyMapTransaction`
+
+use super::FrequencyMap;
+
+use crate::hash::HashMap;
+
+use core::{
+    borrow as std_borrow,
+    cmp as std_cmp,
+    hash as std_hash,
+    ops as std_ops,
+};
+
+
+/// A guard, obtained from [`FrequencyMap::begin()`], through which a batch
+/// of mutations may be made to a [`FrequencyMap`] and subsequently either
+/// committed or rolled back.
+///
+/// The guard journals the original count of each key the first time it is
+/// mutated, so its cost is proportional to the number of distinct keys
+/// touched rather than to the size of the map. Rolling back restores each
+/// touched record, and thus `#total()`, exactly.
+///
+/// Dropping the guard without calling [`#commit()`](Self::commit) rolls
+/// back the transaction.
+///
+/// The map may be read through the guard, which dereferences to
+/// [`FrequencyMap`].
+#[derive(Debug)]
+pub struct FrequencyMapTransaction<'a, K>
+where
+    K : Clone + std_cmp::Eq + std_hash::Hash,
+{
+    /// The map being mutated.
+    fm :      &'a mut FrequencyMap<K>,
+    /// The original counts of each key mutated, where 0 denotes absence.
+    journal : HashMap<K, isize>,
+}
+
+// API functions
+
+impl<K : Clone + std_cmp::Eq + std_hash::Hash> FrequencyMap<K> {
+    /// Begins a transaction, obtaining a guard through which mutations may
+    /// be made and subsequently committed or rolled back.
+    pub fn begin(&mut self) -> FrequencyMapTransaction<'_, K> {
+        FrequencyMapTransaction {
+            fm :      self,
+            journal : HashMap::new(),
+        }
+    }
+}
+
+// Mutating methods
+
+impl<K : Clone + std_cmp::Eq + std_hash::Hash> FrequencyMapTransaction<'_, K> {
+    /// Commits the transaction, retaining all mutations made.
+    pub fn commit(mut self) {
+        self.journal.clear();
+    }
+
+    /// Inserts a record with the given `key` and `count`, as
+    /// [`FrequencyMap::insert()`].
+    pub fn insert(
+        &mut self,
+        key : K,
+        count : isize,
+    ) -> Option<isize> {
+        self.journal_(&key);
+
+        self.fm.insert(key, count)
+    }
+
+    /// Updates the count by 1 of an existing record identified by `key`, or
+    /// creates, with a count of 1, a new record, as
+    /// [`FrequencyMap::push()`].
+    pub fn push(
+        &mut self,
+        key : K,
+    ) {
+        self.journal_(&key);
+
+        self.fm.push(key);
+    }
+
+    /// Updates the count by `count` of an existing record identifed by
+    /// `key`, or creates, with the given `count`, a new record, as
+    /// [`FrequencyMap::push_n()`].
+    pub fn push_n(
+        &mut self,
+        key : K,
+        count : isize,
+    ) {
+        self.journal_(&key);
+
+        self.fm.push_n(key, count);
+    }
+
+    /// Removes a key from the map, returning the count of the key if the
+    /// key was previously in the map, as [`FrequencyMap::remove()`].
+    pub fn remove<Q>(
+        &mut self,
+        key : &Q,
+    ) -> Option<isize>
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        let (key, count) = self.fm.remove_entry(key)?;
+
+        self.journal.entry(key).or_insert(count);
+
+        Some(count)
+    }
+
+    /// Rolls back the transaction, restoring every record mutated through
+    /// the guard to its original count.
+    #[inline]
+    pub fn rollback(self) {
+        // rollback is effected by `Drop`
+    }
+}
+
+// Non-mutating methods
+
+impl<K : Clone + std_cmp::Eq + std_hash::Hash> FrequencyMapTransaction<'_, K> {
+    /// Obtains the number of distinct keys mutated through the guard.
+    #[inline]
+    pub fn journal_len(&self) -> usize {
+        self.journal.len()
+    }
+}
+
+// Implementation
+
+impl<K : Clone + std_cmp::Eq + std_hash::Hash> FrequencyMapTransaction<'_, K> {
+    fn journal_(
+        &mut self,
+        key : &K,
+    ) {
+        if !self.journal.contains_key(key) {
+            self.journal.insert(key.clone(), self.fm.get(key));
+        }
+    }
+}
+
+// Trait implementations
+
+impl<K : Clone + std_cmp::Eq + std_hash::Hash> std_ops::Deref for FrequencyMapTransaction<'_, K> {
+    type Target = FrequencyMap<K>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.fm
+    }
+}
+
+impl<K : Clone + std_cmp::Eq + std_hash::Hash> Drop for FrequencyMapTransaction<'_, K> {
+    /// Rolls back any uncommitted mutations.
+    fn drop(&mut self) {
+        for (key, count) in self.journal.drain() {
+            self.fm.insert(key, count);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use crate::containers::FrequencyMap;
+
+
+    fn make_fm() -> FrequencyMap<&'static str> {
+        FrequencyMap::from([
+            // insert list
+            ("cat", 2),
+            ("dog", 3),
+            ("eel", -1),
+        ])
+    }
+
+    #[test]
+    fn TEST_FrequencyMapTransaction_commit() {
+        let mut fm = make_fm();
+
+        {
+            let mut tx = fm.begin();
+
+            tx.push("cat");
+            tx.push_n("fox", 4);
+            tx.insert("dog", 10);
+            tx.remove("eel");
+
+            assert_eq!(4, tx.journal_len());
+            assert_eq!(17, tx.total());
+            assert_eq!(3, tx["cat"]);
+
+            tx.commit();
+        }
+
+        assert_eq!(3, fm.len());
+        assert_eq!(17, fm.total());
+        assert_eq!(3, fm["cat"]);
+        assert_eq!(10, fm["dog"]);
+        assert_eq!(0, fm["eel"]);
+        assert_eq!(4, fm["fox"]);
+    }
+
+    #[test]
+    fn TEST_FrequencyMapTransaction_rollback() {
+        let mut fm = make_fm();
+
+        {
+            let mut tx = fm.begin();
+
+            tx.push("cat");
+            tx.push_n("cat", 5);
+            tx.push_n("fox", 4);
+            tx.push_n("dog", -3);
+            tx.insert("dog", 7);
+            tx.remove("eel");
+            tx.push("eel");
+            tx.remove("mouse");
+
+            assert_eq!(4, tx.journal_len());
+
+            tx.rollback();
+        }
+
+        assert_eq!(3, fm.len());
+        assert_eq!(4, fm.total());
+        assert_eq!(2, fm["cat"]);
+        assert_eq!(3, fm["dog"]);
+        assert_eq!(-1, fm["eel"]);
+        assert!(!fm.contains_key("fox"));
+    }
+
+    #[test]
+    fn TEST_FrequencyMapTransaction_DROP_ROLLS_BACK() {
+        let mut fm = make_fm();
+
+        {
+            let mut tx = fm.begin();
+
+            tx.push_n("cat", -2);
+            tx.push("mouse");
+        }
+
+        assert_eq!(3, fm.len());
+        assert_eq!(4, fm.total());
+        assert_eq!(2, fm["cat"]);
+        assert!(!fm.contains_key("mouse"));
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
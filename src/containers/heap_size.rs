@@ -0,0 +1,291 @@
+// containers/heap_size.rs : `HeapSize`, `UnicodePointMapHeapSize`
+
+use super::{
+    FrequencyMap,
+    UnicodePointMap,
+};
+
+use crate::hash::table_heap_size_bytes;
+
+use alloc::{
+    boxed::Box,
+    string::String,
+    vec::Vec,
+};
+
+use core::mem as std_mem;
+
+
+/// Reports the heap memory owned by a value, beyond its own (inline)
+/// size, so that the heap usage of containers of such values may be
+/// obtained by [`FrequencyMap::heap_size_bytes()`].
+///
+/// Types that own no heap memory should return 0, which is what the
+/// provided implementation does.
+pub trait HeapSize {
+    /// Obtains the number of bytes of heap memory owned by the instance.
+    fn heap_size_bytes(&self) -> usize {
+        0
+    }
+}
+
+macro_rules! impl_heap_size_for_inline_types_ {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl HeapSize for $t {}
+        )*
+    };
+}
+
+impl_heap_size_for_inline_types_!(
+    (),
+    bool,
+    char,
+    f32,
+    f64,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+);
+
+impl<T : ?Sized> HeapSize for &T {}
+
+impl HeapSize for String {
+    #[inline]
+    fn heap_size_bytes(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl HeapSize for Box<str> {
+    #[inline]
+    fn heap_size_bytes(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T : HeapSize> HeapSize for Box<T> {
+    fn heap_size_bytes(&self) -> usize {
+        std_mem::size_of::<T>() + (**self).heap_size_bytes()
+    }
+}
+
+impl<T : HeapSize> HeapSize for Vec<T> {
+    fn heap_size_bytes(&self) -> usize {
+        self.capacity() * std_mem::size_of::<T>() + self.iter().map(HeapSize::heap_size_bytes).sum::<usize>()
+    }
+}
+
+impl<T : HeapSize> HeapSize for Option<T> {
+    fn heap_size_bytes(&self) -> usize {
+        self.as_ref().map_or(0, HeapSize::heap_size_bytes)
+    }
+}
+
+impl<T : HeapSize, const N : usize> HeapSize for [T; N] {
+    fn heap_size_bytes(&self) -> usize {
+        self.iter().map(HeapSize::heap_size_bytes).sum()
+    }
+}
+
+impl<T0 : HeapSize, T1 : HeapSize> HeapSize for (T0, T1) {
+    fn heap_size_bytes(&self) -> usize {
+        self.0.heap_size_bytes() + self.1.heap_size_bytes()
+    }
+}
+
+
+/// The heap memory used by a [`UnicodePointMap`], broken down between its
+/// contiguous storage and its hash map, as obtained from
+/// [`UnicodePointMap::heap_size_breakdown()`].
+///
+/// A large `hashed_records` relative to `contiguous_records` suggests that
+/// a higher `default_contiguous_ceiling` would be beneficial, whereas few
+/// `contiguous_records` relative to the size of `contiguous_bytes`
+/// suggests that a lower one would be.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub struct UnicodePointMapHeapSize {
+    /// The number of bytes of the contiguous storage.
+    pub contiguous_bytes :   usize,
+    /// The number of records held in the contiguous storage.
+    pub contiguous_records : usize,
+    /// The (estimated) number of bytes of the hash map.
+    pub hashed_bytes :       usize,
+    /// The number of records held in the hash map.
+    pub hashed_records :     usize,
+}
+
+impl UnicodePointMapHeapSize {
+    /// The total number of bytes of heap memory.
+    #[inline]
+    pub fn total_bytes(&self) -> usize {
+        self.contiguous_bytes + self.hashed_bytes
+    }
+}
+
+
+// FrequencyMap
+
+impl<K> FrequencyMap<K> {
+    /// Obtains the (estimated) number of bytes of heap memory used by the
+    /// instance's hash table, not including any heap memory owned by the
+    /// keys.
+    ///
+    /// The estimate reflects the allocation strategy of the hash table
+    /// implementation, and so excludes any allocator overhead.
+    #[inline]
+    pub fn table_heap_size_bytes(&self) -> usize {
+        table_heap_size_bytes::<K, isize>(self.capacity())
+    }
+}
+
+impl<K : HeapSize> FrequencyMap<K> {
+    /// Obtains the (estimated) number of bytes of heap memory used by the
+    /// instance, including its hash table, as obtained by
+    /// [`#table_heap_size_bytes()`](Self::table_heap_size_bytes), and the
+    /// heap memory owned by its keys, as reported by [`HeapSize`].
+    pub fn heap_size_bytes(&self) -> usize {
+        self.table_heap_size_bytes() + self.iter().map(|(key, _)| key.heap_size_bytes()).sum::<usize>()
+    }
+}
+
+impl<K : HeapSize> HeapSize for FrequencyMap<K> {
+    #[inline]
+    fn heap_size_bytes(&self) -> usize {
+        FrequencyMap::heap_size_bytes(self)
+    }
+}
+
+
+// UnicodePointMap
+
+impl UnicodePointMap {
+    /// Obtains the (estimated) number of bytes of heap memory used by the
+    /// instance, across both its contiguous storage and its hash map.
+    #[inline]
+    pub fn heap_size_bytes(&self) -> usize {
+        self.heap_size_breakdown().total_bytes()
+    }
+
+    /// Obtains the (estimated) heap memory used by the instance, broken
+    /// down between its contiguous storage and its hash map, along with the
+    /// number of records held in each, for use in tuning the
+    /// `default_contiguous_ceiling` argument of [`UnicodePointMap::new()`].
+    pub fn heap_size_breakdown(&self) -> UnicodePointMapHeapSize {
+        let (vec, map) = self.storage_();
+
+        UnicodePointMapHeapSize {
+            contiguous_bytes :   std_mem::size_of_val(vec),
+            contiguous_records : vec.iter().filter(|&&count| 0 != count).count(),
+            hashed_bytes :       table_heap_size_bytes::<char, isize>(map.capacity()),
+            hashed_records :     map.len(),
+        }
+    }
+}
+
+impl HeapSize for UnicodePointMap {
+    #[inline]
+    fn heap_size_bytes(&self) -> usize {
+        UnicodePointMap::heap_size_bytes(self)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::{
+        HeapSize,
+        UnicodePointMapHeapSize,
+    };
+
+    use crate::containers::{
+        FrequencyMap,
+        UnicodePointMap,
+    };
+
+    use std::mem::size_of;
+
+
+    #[test]
+    fn TEST_HeapSize_PROVIDED_IMPLEMENTATIONS() {
+        assert_eq!(0, 123i32.heap_size_bytes());
+        assert_eq!(0, "abc".heap_size_bytes());
+        assert_eq!(16, String::with_capacity(16).heap_size_bytes());
+        assert_eq!(4 * size_of::<u64>(), Vec::<u64>::with_capacity(4).heap_size_bytes());
+        assert_eq!(2 * size_of::<String>() + 8 + 3, vec![String::with_capacity(8), String::from("abc")].heap_size_bytes());
+        assert_eq!(3, Some(String::from("abc")).heap_size_bytes());
+        assert_eq!(0, None::<String>.heap_size_bytes());
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_heap_size_bytes_1() {
+        let mut fm = FrequencyMap::<String>::new();
+
+        assert_eq!(0, fm.table_heap_size_bytes());
+        assert_eq!(0, fm.heap_size_bytes());
+
+        fm.push(String::with_capacity(100));
+        fm.push(String::from("abc"));
+
+        let table = fm.table_heap_size_bytes();
+
+        assert!(table >= 2 * size_of::<(String, isize)>());
+        assert_eq!(table + 100 + 3, fm.heap_size_bytes());
+
+        let fm = FrequencyMap::<u8>::from_iter(0..=255);
+
+        // at least one entry and control byte per record, and no more than
+        // the next power of two beyond 8/7ths thereof
+        assert!(fm.table_heap_size_bytes() >= 256 * (size_of::<(u8, isize)>() + 1));
+        assert!(fm.table_heap_size_bytes() <= 512 * (size_of::<(u8, isize)>() + 1) + 16);
+        assert_eq!(fm.table_heap_size_bytes(), fm.heap_size_bytes());
+    }
+
+    #[test]
+    fn TEST_UnicodePointMap_heap_size_breakdown_1() {
+        let mut upm = UnicodePointMap::new('\u{100}');
+
+        assert_eq!(
+            UnicodePointMapHeapSize {
+                contiguous_bytes :   0x100 * size_of::<isize>(),
+                contiguous_records : 0,
+                hashed_bytes :       0,
+                hashed_records :     0,
+            },
+            upm.heap_size_breakdown()
+        );
+
+        upm.push_n('a', 3);
+        upm.push('é');
+        upm.push('€');
+        upm.push('😀');
+
+        let breakdown = upm.heap_size_breakdown();
+
+        assert_eq!(0x100 * size_of::<isize>(), breakdown.contiguous_bytes);
+        assert_eq!(2, breakdown.contiguous_records);
+        assert!(breakdown.hashed_bytes >= 2 * size_of::<(char, isize)>());
+        assert_eq!(2, breakdown.hashed_records);
+        assert_eq!(breakdown.total_bytes(), upm.heap_size_bytes());
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
@@ -0,0 +1,634 @@
+// containers/histogram.rs : `Histogram`
+
+use base_traits::{
+    IsEmpty,
+    Len,
+};
+
+use std::{
+    error as std_error,
+    fmt as std_fmt,
+};
+
+
+/// The strategy by which the bin edges of a [`Histogram`] are determined.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum BinStrategy {
+    /// `bins` bins of equal width spanning `[min, max)`.
+    FixedWidth {
+        min :  f64,
+        max :  f64,
+        bins : usize,
+    },
+    /// `bins` bins whose edges are equally spaced in logarithmic space
+    /// spanning `[min, max)`. `min` must be greater than 0.
+    Logarithmic {
+        min :  f64,
+        max :  f64,
+        bins : usize,
+    },
+    /// Bins defined by the given edges, which must be finite and strictly
+    /// ascending. `n` edges define `n - 1` bins.
+    ExplicitEdges(Vec<f64>),
+}
+
+/// Error type for [`Histogram`] construction and merging.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum HistogramError {
+    /// The number of bins is 0.
+    NoBins,
+    /// The range is empty or reversed, a bound is not finite, or (for
+    /// [`BinStrategy::Logarithmic`]) the lower bound is not positive.
+    InvalidRange,
+    /// The explicit edges are not finite and strictly ascending.
+    InvalidEdges,
+    /// The histograms being merged do not have identical edges.
+    MismatchedEdges,
+}
+
+impl std_fmt::Display for HistogramError {
+    fn fmt(
+        &self,
+        f : &mut std_fmt::Formatter<'_>,
+    ) -> std_fmt::Result {
+        match self {
+            Self::NoBins => f.write_str("histogram must have at least one bin"),
+            Self::InvalidRange => f.write_str("histogram range is invalid"),
+            Self::InvalidEdges => f.write_str("histogram edges must be finite and strictly ascending"),
+            Self::MismatchedEdges => f.write_str("histograms do not have identical edges"),
+        }
+    }
+}
+
+impl std_error::Error for HistogramError {}
+
+
+/// A numeric value that may be counted by a [`Histogram`], being any of
+/// the primitive integer and floating-point types.
+///
+/// Integers whose magnitude exceeds 2^53 are rounded to the nearest
+/// representable `f64`.
+pub trait Measurement {
+    /// Converts the value to `f64`.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_measurement_ {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl Measurement for $t {
+                #[inline]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_measurement_!(
+    f32,
+    f64,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+);
+
+
+/// A container that counts numeric measurements into bins.
+///
+/// Each bin `i` covers the half-open interval `[edges[i], edges[i + 1])`.
+/// Values below the first edge are counted in the underflow bin, and
+/// values at or above the last edge are counted in the overflow bin. NaN
+/// values are ignored.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct Histogram {
+    /// The bin edges, of which there is one more than there are bins.
+    edges :     Vec<f64>,
+    /// The count of each bin.
+    counts :    Vec<isize>,
+    /// The count of values below the first edge.
+    underflow : isize,
+    /// The count of values at or above the last edge.
+    overflow :  isize,
+    /// The total number of values represented.
+    total :     isize,
+    /// The sum of all values represented.
+    sum :       f64,
+}
+
+// API functions
+
+impl Histogram {
+    /// Creates a new empty instance whose bins are determined by the given
+    /// `strategy`.
+    pub fn new(strategy : BinStrategy) -> Result<Self, HistogramError> {
+        let edges = match strategy {
+            BinStrategy::FixedWidth {
+                min,
+                max,
+                bins,
+            } => {
+                Self::check_range_(min, max, bins)?;
+
+                let width = (max - min) / bins as f64;
+
+                (0..=bins)
+                    .map(|i| if i == bins { max } else { min + width * i as f64 })
+                    .collect()
+            },
+            BinStrategy::Logarithmic {
+                min,
+                max,
+                bins,
+            } => {
+                Self::check_range_(min, max, bins)?;
+
+                if min <= 0.0 {
+                    return Err(HistogramError::InvalidRange);
+                }
+
+                let log_min = min.ln();
+                let log_width = (max.ln() - log_min) / bins as f64;
+
+                (0..=bins)
+                    .map(|i| {
+                        match i {
+                            0 => min,
+                            _ if i == bins => max,
+                            _ => (log_min + log_width * i as f64).exp(),
+                        }
+                    })
+                    .collect()
+            },
+            BinStrategy::ExplicitEdges(edges) => {
+                if edges.len() < 2 {
+                    return Err(HistogramError::NoBins);
+                }
+
+                if edges.iter().any(|e| !e.is_finite()) || edges.windows(2).any(|w| w[0] >= w[1]) {
+                    return Err(HistogramError::InvalidEdges);
+                }
+
+                edges
+            },
+        };
+
+        Ok(Self::from_edges_(edges))
+    }
+}
+
+// Mutating methods
+
+impl Histogram {
+    /// Clears the histogram, resetting all bins and `#total()`.
+    pub fn clear(&mut self) {
+        self.counts.fill(0);
+        self.underflow = 0;
+        self.overflow = 0;
+        self.total = 0;
+        self.sum = 0.0;
+    }
+
+    /// Adds the counts of `other` into `self`.
+    ///
+    /// # Errors
+    /// [`HistogramError::MismatchedEdges`] if `other` does not have
+    /// identical edges to `self`, in which case `self` is unchanged.
+    pub fn merge(
+        &mut self,
+        other : &Self,
+    ) -> Result<(), HistogramError> {
+        if self.edges != other.edges {
+            return Err(HistogramError::MismatchedEdges);
+        }
+
+        for (count, &other_count) in self.counts.iter_mut().zip(other.counts.iter()) {
+            *count += other_count;
+        }
+
+        self.underflow += other.underflow;
+        self.overflow += other.overflow;
+        self.total += other.total;
+        self.sum += other.sum;
+
+        Ok(())
+    }
+
+    /// Counts the given `value` once.
+    #[inline]
+    pub fn push<V : Measurement>(
+        &mut self,
+        value : V,
+    ) {
+        self.push_n(value, 1);
+    }
+
+    /// Counts the given `value` `count` times.
+    pub fn push_n<V : Measurement>(
+        &mut self,
+        value : V,
+        count : isize,
+    ) {
+        let value = value.to_f64();
+
+        if 0 == count || value.is_nan() {
+            return;
+        }
+
+        let last = self.edges.len() - 1;
+
+        if value < self.edges[0] {
+            self.underflow += count;
+        } else if value >= self.edges[last] {
+            self.overflow += count;
+        } else {
+            let ix = self.edges.partition_point(|&e| e <= value) - 1;
+
+            self.counts[ix] += count;
+        }
+
+        self.total += count;
+        self.sum += value * count as f64;
+    }
+}
+
+// Non-mutating methods
+
+impl Histogram {
+    /// The counts of each bin, excluding underflow and overflow.
+    #[inline]
+    pub fn bin_counts(&self) -> &[isize] {
+        &self.counts
+    }
+
+    /// The bin edges, of which there is one more than there are bins.
+    #[inline]
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// The arithmetic mean of all values represented, or `None` if the
+    /// histogram is empty.
+    pub fn mean(&self) -> Option<f64> {
+        if 0 == self.total {
+            None
+        } else {
+            Some(self.sum / self.total as f64)
+        }
+    }
+
+    /// The count of values at or above the last edge.
+    #[inline]
+    pub fn overflow(&self) -> isize {
+        self.overflow
+    }
+
+    /// Estimates the value below which the fraction `q` of all values
+    /// lie, by linear interpolation within the bin containing that rank.
+    ///
+    /// Ranks falling in the underflow (overflow) bin obtain the first
+    /// (last) edge. Obtains `None` if `q` is not in `[0, 1]` or the
+    /// histogram has no positive total.
+    pub fn quantile(
+        &self,
+        q : f64,
+    ) -> Option<f64> {
+        if !(0.0..=1.0).contains(&q) || self.total <= 0 {
+            return None;
+        }
+
+        let last = self.edges.len() - 1;
+        let target = q * self.total as f64;
+        let mut cumulative = self.underflow as f64;
+
+        if target < cumulative {
+            return Some(self.edges[0]);
+        }
+
+        for (ix, &count) in self.counts.iter().enumerate() {
+            let count = count as f64;
+
+            if count > 0.0 && target <= cumulative + count {
+                let fraction = (target - cumulative) / count;
+
+                return Some(self.edges[ix] + fraction * (self.edges[ix + 1] - self.edges[ix]));
+            }
+
+            cumulative += count;
+        }
+
+        Some(self.edges[last])
+    }
+
+    /// The count of values below the first edge.
+    #[inline]
+    pub fn underflow(&self) -> isize {
+        self.underflow
+    }
+}
+
+impl Histogram {
+    /// Indicates whether the histogram represents no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.is_empty_()
+    }
+
+    /// Obtains the number of bins, excluding underflow and overflow.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len_()
+    }
+
+    /// Indicates the total frequency count across all bins, including
+    /// underflow and overflow.
+    #[inline]
+    pub fn total(&self) -> isize {
+        self.total
+    }
+}
+
+// Implementation
+
+impl Histogram {
+    fn check_range_(
+        min : f64,
+        max : f64,
+        bins : usize,
+    ) -> Result<(), HistogramError> {
+        if 0 == bins {
+            Err(HistogramError::NoBins)
+        } else if !min.is_finite() || !max.is_finite() || min >= max {
+            Err(HistogramError::InvalidRange)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn from_edges_(edges : Vec<f64>) -> Self {
+        let counts = vec![0; edges.len() - 1];
+        let underflow = 0;
+        let overflow = 0;
+        let total = 0;
+        let sum = 0.0;
+
+        Self {
+            edges,
+            counts,
+            underflow,
+            overflow,
+            total,
+            sum,
+        }
+    }
+
+    #[inline]
+    fn is_empty_(&self) -> bool {
+        0 == self.total
+    }
+
+    #[inline]
+    fn len_(&self) -> usize {
+        self.counts.len()
+    }
+}
+
+// Trait implementations
+
+impl IsEmpty for Histogram {
+    /// Indicates whether the histogram represents no values.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.is_empty_()
+    }
+}
+
+impl Len for Histogram {
+    /// Obtains the number of bins, excluding underflow and overflow.
+    #[inline]
+    fn len(&self) -> usize {
+        self.len_()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::{
+        BinStrategy,
+        Histogram,
+        HistogramError,
+    };
+
+
+    fn fixed_0_10_5() -> Histogram {
+        Histogram::new(BinStrategy::FixedWidth {
+            min :  0.0,
+            max :  10.0,
+            bins : 5,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn TEST_Histogram_new_FixedWidth() {
+        let h = fixed_0_10_5();
+
+        assert!(h.is_empty());
+        assert_eq!(5, h.len());
+        assert_eq!(0, h.total());
+        assert_eq!(&[0.0, 2.0, 4.0, 6.0, 8.0, 10.0], h.edges());
+        assert_eq!(None, h.mean());
+        assert_eq!(None, h.quantile(0.5));
+
+        {
+            let l : &dyn base_traits::Len = &h;
+
+            assert_eq!(5, l.len());
+        }
+    }
+
+    #[test]
+    fn TEST_Histogram_new_Logarithmic() {
+        let h = Histogram::new(BinStrategy::Logarithmic {
+            min :  1.0,
+            max :  1000.0,
+            bins : 3,
+        })
+        .unwrap();
+
+        assert_eq!(3, h.len());
+        assert_eq!(4, h.edges().len());
+        assert_eq!(1.0, h.edges()[0]);
+        assert!((h.edges()[1] - 10.0).abs() < 1e-9);
+        assert!((h.edges()[2] - 100.0).abs() < 1e-9);
+        assert_eq!(1000.0, h.edges()[3]);
+    }
+
+    #[test]
+    fn TEST_Histogram_new_INVALID() {
+        assert_eq!(
+            Some(HistogramError::NoBins),
+            Histogram::new(BinStrategy::FixedWidth {
+                min :  0.0,
+                max :  1.0,
+                bins : 0,
+            })
+            .err()
+        );
+        assert_eq!(
+            Some(HistogramError::InvalidRange),
+            Histogram::new(BinStrategy::FixedWidth {
+                min :  1.0,
+                max :  1.0,
+                bins : 2,
+            })
+            .err()
+        );
+        assert_eq!(
+            Some(HistogramError::InvalidRange),
+            Histogram::new(BinStrategy::Logarithmic {
+                min :  0.0,
+                max :  10.0,
+                bins : 2,
+            })
+            .err()
+        );
+        assert_eq!(
+            Some(HistogramError::InvalidEdges),
+            Histogram::new(BinStrategy::ExplicitEdges(vec![0.0, 2.0, 1.0])).err()
+        );
+        assert_eq!(
+            Some(HistogramError::NoBins),
+            Histogram::new(BinStrategy::ExplicitEdges(vec![0.0])).err()
+        );
+    }
+
+    #[test]
+    fn TEST_Histogram_push_1() {
+        let mut h = fixed_0_10_5();
+
+        h.push(-1.0);
+        h.push(0);
+        h.push(1.5);
+        h.push(2);
+        h.push_n(9.99, 3);
+        h.push(10);
+        h.push(f64::NAN);
+
+        assert!(!h.is_empty());
+        assert_eq!(8, h.total());
+        assert_eq!(1, h.underflow());
+        assert_eq!(1, h.overflow());
+        assert_eq!(&[2, 1, 0, 0, 3], h.bin_counts());
+
+        let expected_mean = (-1.0 + 0.0 + 1.5 + 2.0 + 3.0 * 9.99 + 10.0) / 8.0;
+
+        assert!((h.mean().unwrap() - expected_mean).abs() < 1e-9);
+
+        h.clear();
+
+        assert!(h.is_empty());
+        assert_eq!(&[0, 0, 0, 0, 0], h.bin_counts());
+    }
+
+    #[test]
+    fn TEST_Histogram_push_ExplicitEdges() {
+        let mut h = Histogram::new(BinStrategy::ExplicitEdges(vec![0.0, 1.0, 10.0, 100.0])).unwrap();
+
+        for v in [0, 5, 50, 99, 100, 7] {
+            h.push(v);
+        }
+
+        assert_eq!(&[1, 2, 2], h.bin_counts());
+        assert_eq!(0, h.underflow());
+        assert_eq!(1, h.overflow());
+    }
+
+    #[test]
+    fn TEST_Histogram_push_INTEGER_TYPES() {
+        let mut h = Histogram::new(BinStrategy::ExplicitEdges(vec![0.0, 1_000.0, 1_000_000.0])).unwrap();
+
+        h.push(512usize);
+        h.push(4_096u64);
+        h.push_n(1_500_000u64, 2);
+        h.push(-3i64);
+        h.push(999isize);
+        h.push(7u8);
+        h.push(2.5f32);
+
+        assert_eq!(&[4, 1], h.bin_counts());
+        assert_eq!(1, h.underflow());
+        assert_eq!(2, h.overflow());
+        assert_eq!(8, h.total());
+    }
+
+    #[test]
+    fn TEST_Histogram_quantile_1() {
+        let mut h = fixed_0_10_5();
+
+        h.push_n(1.0, 10);
+        h.push_n(5.0, 10);
+
+        assert_eq!(Some(0.0), h.quantile(0.0));
+        assert_eq!(Some(2.0), h.quantile(0.5));
+        assert_eq!(Some(5.0), h.quantile(0.75));
+        assert_eq!(Some(6.0), h.quantile(1.0));
+        assert_eq!(None, h.quantile(1.5));
+        assert_eq!(None, h.quantile(f64::NAN));
+
+        h.push_n(-5.0, 20);
+
+        assert_eq!(Some(0.0), h.quantile(0.25));
+
+        h.push_n(50.0, 40);
+
+        assert_eq!(Some(10.0), h.quantile(0.9));
+    }
+
+    #[test]
+    fn TEST_Histogram_merge_1() {
+        let mut h1 = fixed_0_10_5();
+        let mut h2 = fixed_0_10_5();
+
+        h1.push(1);
+        h1.push(-1);
+        h2.push(1);
+        h2.push(3);
+        h2.push(11);
+
+        assert_eq!(Ok(()), h1.merge(&h2));
+
+        assert_eq!(5, h1.total());
+        assert_eq!(1, h1.underflow());
+        assert_eq!(1, h1.overflow());
+        assert_eq!(&[2, 1, 0, 0, 0], h1.bin_counts());
+        assert!((h1.mean().unwrap() - 3.0).abs() < 1e-9);
+
+        let h3 = Histogram::new(BinStrategy::ExplicitEdges(vec![0.0, 10.0])).unwrap();
+
+        assert_eq!(Err(HistogramError::MismatchedEdges), h1.merge(&h3));
+        assert_eq!(5, h1.total());
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
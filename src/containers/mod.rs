@@ -3,8 +3,40 @@
 use crate::macros::declare_and_publish;
 
 
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+declare_and_publish!(arbitrary, FrequencyMapParameters, UnicodePointMapParameters);
+#[cfg(feature = "proptest")]
+pub use arbitrary::{
+    frequency_map_strategy,
+    unicode_point_map_strategy,
+};
+#[cfg(feature = "quickcheck")]
+pub use arbitrary::{
+    arbitrary_frequency_map,
+    arbitrary_unicode_point_map,
+};
+declare_and_publish!(bidirectional_frequency_map, BidirectionalFrequencyMap);
+#[cfg(feature = "std")]
+declare_and_publish!(binary_format, BinaryFormatError, BinaryKey);
+declare_and_publish!(byte_frequency_map, ByteCountOverflowError, ByteFrequencyMap);
+declare_and_publish!(conditional_frequency_map, ConditionalFrequencyMap);
+declare_and_publish!(dense_frequency_map, DenseFrequencyMap, DenseFrequencyMapIter, DenseKey);
+declare_and_publish!(frequency_delta, FrequencyDelta);
 declare_and_publish!(frequency_map, FrequencyMap);
+declare_and_publish!(frequency_map_transaction, FrequencyMapTransaction);
+declare_and_publish!(heap_size, HeapSize, UnicodePointMapHeapSize);
+#[cfg(feature = "std")]
+declare_and_publish!(histogram, BinStrategy, Histogram, HistogramError, Measurement);
+declare_and_publish!(observable_frequency_map, CrossingDirection, ObservableFrequencyMap, ThresholdCrossing);
+declare_and_publish!(pruning, PruneSummary);
+declare_and_publish!(ranking, RankIndex);
+declare_and_publish!(small_frequency_map, SmallFrequencyMap, SmallFrequencyMapIter);
+#[cfg(feature = "std")]
+declare_and_publish!(text_format, CodePointFormat, ImportError);
 declare_and_publish!(unicode_point_map, UnicodePointMap);
+declare_and_publish!(validation, InvariantError);
+
+mod rollup;
 
 
 // ///////////////////////////// end of file //////////////////////////// //
@@ -0,0 +1,573 @@
+// containers/observable_frequency_map.rs : `ObservableFrequencyMap`
+
+use super::FrequencyMap;
+
+use crate::hash::{
+    hash_map,
+    HashMap,
+};
+
+use base_traits::{
+    IsEmpty,
+    Len,
+};
+
+use alloc::{
+    boxed::Box,
+    vec::Vec,
+};
+
+use core::{
+    borrow as std_borrow,
+    cmp as std_cmp,
+    fmt as std_fmt,
+    hash as std_hash,
+    ops as std_ops,
+};
+
+
+/// The direction in which a count crossed a threshold.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub enum CrossingDirection {
+    /// The count rose from below the threshold to at or above it.
+    Upward,
+    /// The count fell from at or above the threshold to below it.
+    Downward,
+}
+
+/// Describes a count crossing a threshold, as passed to the callbacks of
+/// an [`ObservableFrequencyMap`].
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub struct ThresholdCrossing {
+    /// The threshold that was crossed.
+    pub threshold : isize,
+    /// The direction in which it was crossed.
+    pub direction : CrossingDirection,
+    /// The count before the mutation.
+    pub previous :  isize,
+    /// The count after the mutation.
+    pub current :   isize,
+}
+
+type CountCallback<K> = Box<dyn FnMut(&K, isize)>;
+type ThresholdCallback<K> = Box<dyn FnMut(&K, &ThresholdCrossing)>;
+
+
+/// A [`FrequencyMap`] wrapper that invokes registered callbacks when a key
+/// first appears, when a record is removed, and when a key's count crosses
+/// a threshold.
+///
+/// A count is deemed to cross threshold `t` upward when it changes from
+/// below `t` to at or above `t`, and downward when it changes from at or
+/// above `t` to below `t`. Absent keys have a count of 0, so a record
+/// removed by [`#remove()`](Self::remove) or by its count reaching 0 may
+/// also cross thresholds.
+///
+/// Callbacks are invoked synchronously, before the mutation that triggered
+/// them is applied, by all mutating methods. Hence, should a callback
+/// panic, the map is left unmodified.
+pub struct ObservableFrequencyMap<K> {
+    /// The observed map.
+    fm :        FrequencyMap<K>,
+    /// The registered callbacks.
+    callbacks : Callbacks<K>,
+}
+
+/// The callbacks of an [`ObservableFrequencyMap`], held apart from the
+/// observed map so that they may be invoked while it is borrowed.
+struct Callbacks<K> {
+    /// Callbacks invoked when a key first appears.
+    appear :            Vec<CountCallback<K>>,
+    /// Callbacks invoked when a record is removed.
+    remove :            Vec<CountCallback<K>>,
+    /// Thresholds, and their callbacks, that apply to all keys.
+    global_thresholds : Vec<(isize, ThresholdCallback<K>)>,
+    /// Thresholds, and their callbacks, that apply to specific keys.
+    key_thresholds :    HashMap<K, Vec<(isize, ThresholdCallback<K>)>>,
+}
+
+// API functions
+
+impl<K> ObservableFrequencyMap<K> {
+    /// Creates a new empty instance with no callbacks registered.
+    pub fn new() -> Self {
+        Self::from(FrequencyMap::new())
+    }
+}
+
+// Mutating methods
+
+impl<K : std_cmp::Eq + std_hash::Hash> ObservableFrequencyMap<K> {
+    /// Inserts a record with the given `key` and `count`, replacing any
+    /// existing record with that `key`, as [`FrequencyMap::insert()`].
+    pub fn insert(
+        &mut self,
+        key : K,
+        count : isize,
+    ) -> Option<isize> {
+        let prev = self.fm.get(&key);
+
+        self.callbacks.notify_(&key, prev, count);
+
+        self.fm.insert(key, count)
+    }
+
+    /// Registers a callback to be invoked, with the key and its count,
+    /// when a key first appears.
+    pub fn on_appear<F>(
+        &mut self,
+        f : F,
+    ) where
+        F : FnMut(&K, isize) + 'static,
+    {
+        self.callbacks.appear.push(Box::new(f));
+    }
+
+    /// Registers a callback to be invoked when the count of the given
+    /// `key` crosses `threshold` in either direction.
+    pub fn on_key_threshold<F>(
+        &mut self,
+        key : K,
+        threshold : isize,
+        f : F,
+    ) where
+        F : FnMut(&K, &ThresholdCrossing) + 'static,
+    {
+        self.callbacks.key_thresholds.entry(key).or_default().push((threshold, Box::new(f)));
+    }
+
+    /// Registers a callback to be invoked, with the key and its count prior
+    /// to removal, when a record is removed, whether because its count
+    /// reached 0 or by [`#remove()`](Self::remove).
+    pub fn on_remove<F>(
+        &mut self,
+        f : F,
+    ) where
+        F : FnMut(&K, isize) + 'static,
+    {
+        self.callbacks.remove.push(Box::new(f));
+    }
+
+    /// Registers a callback to be invoked when the count of any key
+    /// crosses `threshold` in either direction.
+    pub fn on_threshold<F>(
+        &mut self,
+        threshold : isize,
+        f : F,
+    ) where
+        F : FnMut(&K, &ThresholdCrossing) + 'static,
+    {
+        self.callbacks.global_thresholds.push((threshold, Box::new(f)));
+    }
+
+    /// Updates the count by 1 of an existing record identified by `key`, or
+    /// creates, with a count of 1, a new record.
+    #[inline]
+    pub fn push(
+        &mut self,
+        key : K,
+    ) {
+        self.push_n(key, 1);
+    }
+
+    /// Updates the count by `count` of an existing record identifed by
+    /// `key`, or creates, with the given `count`, a new record.
+    ///
+    /// In the case that the resulting count of an existing record is 0 then
+    /// the record is removed.
+    pub fn push_n(
+        &mut self,
+        key : K,
+        count : isize,
+    ) {
+        if 0 != count {
+            let prev = self.fm.get(&key);
+
+            self.callbacks.notify_(&key, prev, prev + count);
+
+            self.fm.push_n(key, count);
+        }
+    }
+
+    /// Removes a key from the map, returning the count of the key if the
+    /// key was previously in the map.
+    pub fn remove<Q>(
+        &mut self,
+        key : &Q,
+    ) -> Option<isize>
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        let (stored_key, count) = self.fm.get_key_value_(key)?;
+
+        self.callbacks.notify_(stored_key, count, 0);
+
+        self.fm.remove(key)
+    }
+}
+
+// Non-mutating methods
+
+impl<K : std_cmp::Eq + std_hash::Hash> ObservableFrequencyMap<K> {
+    /// Indicates whether a record exists for the given `key`.
+    #[inline]
+    pub fn contains_key<Q>(
+        &self,
+        key : &Q,
+    ) -> bool
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        self.fm.contains_key(key)
+    }
+
+    /// Obtains the count corresponding to the given `key`, obtaining 0 in
+    /// the case that no such record exists.
+    #[inline]
+    pub fn get<Q>(
+        &self,
+        key : &Q,
+    ) -> isize
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        self.fm.get(key)
+    }
+}
+
+impl<K> ObservableFrequencyMap<K> {
+    /// Obtains a reference to the observed [`FrequencyMap`].
+    #[inline]
+    pub fn as_frequency_map(&self) -> &FrequencyMap<K> {
+        &self.fm
+    }
+
+    /// Consumes the instance, discarding all callbacks and obtaining the
+    /// observed [`FrequencyMap`].
+    #[inline]
+    pub fn into_inner(self) -> FrequencyMap<K> {
+        self.fm
+    }
+
+    /// An iterator visiting all key-count pairs in arbitrary order. The
+    /// iterator element type is `(&'a K, &'a isize)`.
+    #[inline]
+    pub fn iter(&self) -> hash_map::Iter<'_, K, isize> {
+        self.fm.iter()
+    }
+
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.fm.is_empty()
+    }
+
+    /// Obtains the number of records.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.fm.len()
+    }
+
+    /// Indicates the total frequency count across all records.
+    #[inline]
+    pub fn total(&self) -> isize {
+        self.fm.total()
+    }
+}
+
+// Implementation
+
+impl<K : std_cmp::Eq + std_hash::Hash> Callbacks<K> {
+    fn notify_(
+        &mut self,
+        key : &K,
+        prev : isize,
+        curr : isize,
+    ) {
+        if prev == curr {
+            return;
+        }
+
+        if 0 == prev {
+            for f in &mut self.appear {
+                f(key, curr);
+            }
+        }
+
+        if 0 == curr {
+            for f in &mut self.remove {
+                f(key, prev);
+            }
+        }
+
+        let thresholds = self
+            .global_thresholds
+            .iter_mut()
+            .chain(self.key_thresholds.get_mut(key).into_iter().flat_map(|v| v.iter_mut()));
+
+        for (threshold, f) in thresholds {
+            let direction = if prev < *threshold && curr >= *threshold {
+                CrossingDirection::Upward
+            } else if prev >= *threshold && curr < *threshold {
+                CrossingDirection::Downward
+            } else {
+                continue;
+            };
+
+            f(key, &ThresholdCrossing {
+                threshold : *threshold,
+                direction,
+                previous : prev,
+                current : curr,
+            });
+        }
+    }
+}
+
+// Trait implementations
+
+impl<K : std_fmt::Debug> std_fmt::Debug for ObservableFrequencyMap<K> {
+    fn fmt(
+        &self,
+        f : &mut std_fmt::Formatter<'_>,
+    ) -> std_fmt::Result {
+        f.debug_struct("ObservableFrequencyMap")
+            .field("fm", &self.fm)
+            .field("appear_callbacks", &self.callbacks.appear.len())
+            .field("remove_callbacks", &self.callbacks.remove.len())
+            .field("global_thresholds", &self.callbacks.global_thresholds.len())
+            .field("key_thresholds", &self.callbacks.key_thresholds.len())
+            .finish()
+    }
+}
+
+impl<K> Default for ObservableFrequencyMap<K> {
+    /// Creates an empty instance with no callbacks registered.
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> From<FrequencyMap<K>> for ObservableFrequencyMap<K> {
+    /// Creates an instance that observes the given [`FrequencyMap`], with
+    /// no callbacks registered.
+    fn from(value : FrequencyMap<K>) -> Self {
+        Self {
+            fm :        value,
+            callbacks : Callbacks {
+                appear :            Vec::new(),
+                remove :            Vec::new(),
+                global_thresholds : Vec::new(),
+                key_thresholds :    HashMap::new(),
+            },
+        }
+    }
+}
+
+impl<K, Q> std_ops::Index<&Q> for ObservableFrequencyMap<K>
+where
+    K : std_cmp::Eq + std_hash::Hash + std_borrow::Borrow<Q>,
+    Q : std_cmp::Eq + std_hash::Hash + ?Sized,
+{
+    type Output = isize;
+
+    #[inline]
+    fn index(
+        &self,
+        key : &Q,
+    ) -> &Self::Output {
+        &self.fm[key]
+    }
+}
+
+impl<K> IsEmpty for ObservableFrequencyMap<K> {
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.fm.is_empty()
+    }
+}
+
+impl<K> Len for ObservableFrequencyMap<K> {
+    /// Obtains the number of records.
+    #[inline]
+    fn len(&self) -> usize {
+        self.fm.len()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::{
+        CrossingDirection,
+        ObservableFrequencyMap,
+        ThresholdCrossing,
+    };
+
+    use std::{
+        cell::RefCell,
+        panic,
+        rc::Rc,
+    };
+
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    fn observed(log : &Log) -> ObservableFrequencyMap<&'static str> {
+        let mut ofm = ObservableFrequencyMap::new();
+
+        {
+            let log = log.clone();
+
+            ofm.on_appear(move |key, count| log.borrow_mut().push(format!("appear {key} {count}")));
+        }
+        {
+            let log = log.clone();
+
+            ofm.on_remove(move |key, count| log.borrow_mut().push(format!("remove {key} {count}")));
+        }
+
+        ofm
+    }
+
+    fn describe(
+        key : &str,
+        crossing : &ThresholdCrossing,
+    ) -> String {
+        format!(
+            "{} {key} {} {}->{}",
+            match crossing.direction {
+                CrossingDirection::Upward => "up",
+                CrossingDirection::Downward => "down",
+            },
+            crossing.threshold,
+            crossing.previous,
+            crossing.current
+        )
+    }
+
+    #[test]
+    fn TEST_ObservableFrequencyMap_APPEAR_AND_REMOVE() {
+        let log = Log::default();
+        let mut ofm = observed(&log);
+
+        ofm.push("cat");
+        ofm.push("cat");
+        ofm.push_n("dog", 3);
+        ofm.push_n("cat", -2);
+        ofm.insert("dog", 0);
+        ofm.insert("eel", 4);
+        ofm.remove("eel");
+        ofm.remove("eel");
+
+        assert!(ofm.is_empty());
+        assert_eq!(
+            vec!["appear cat 1", "appear dog 3", "remove cat 2", "remove dog 3", "appear eel 4", "remove eel 4"],
+            *log.borrow()
+        );
+    }
+
+    #[test]
+    fn TEST_ObservableFrequencyMap_THRESHOLDS() {
+        let log = Log::default();
+        let mut ofm = observed(&log);
+
+        {
+            let log = log.clone();
+
+            ofm.on_threshold(3, move |key, crossing| log.borrow_mut().push(describe(key, crossing)));
+        }
+        {
+            let log = log.clone();
+
+            ofm.on_key_threshold("dog", 5, move |key, crossing| log.borrow_mut().push(describe(key, crossing)));
+        }
+
+        ofm.push_n("cat", 2);
+        ofm.push("cat");
+        ofm.push("cat");
+        ofm.push_n("dog", 6);
+        ofm.push_n("dog", -2);
+        ofm.remove("cat");
+
+        assert_eq!(4, ofm.total());
+        assert_eq!(
+            vec![
+                "appear cat 2",
+                "up cat 3 2->3",
+                "appear dog 6",
+                "up dog 3 0->6",
+                "up dog 5 0->6",
+                "down dog 5 6->4",
+                "remove cat 4",
+                "down cat 3 4->0",
+            ],
+            *log.borrow()
+        );
+    }
+
+    #[test]
+    fn TEST_ObservableFrequencyMap_CALLBACKS_PRECEDE_MUTATION() {
+        let log = Log::default();
+        let mut ofm = observed(&log);
+
+        ofm.push_n("cat", 2);
+        ofm.push_n("dog", 3);
+
+        // the callbacks cannot see the map, but a panicking callback shows
+        // whether the mutation had been applied when it was invoked
+        ofm.on_remove(|key, _| panic!("removing {key}"));
+        ofm.on_appear(|key, _| panic!("appearing {key}"));
+
+        let r = panic::catch_unwind(panic::AssertUnwindSafe(|| ofm.remove("cat")));
+
+        assert!(r.is_err());
+        assert_eq!(2, ofm.len());
+        assert_eq!(5, ofm.total());
+        assert_eq!(2, ofm["cat"]);
+
+        let r = panic::catch_unwind(panic::AssertUnwindSafe(|| ofm.push_n("dog", -3)));
+
+        assert!(r.is_err());
+        assert_eq!(3, ofm["dog"]);
+
+        let r = panic::catch_unwind(panic::AssertUnwindSafe(|| ofm.insert("eel", 1)));
+
+        assert!(r.is_err());
+        assert!(!ofm.contains_key("eel"));
+        assert_eq!(5, ofm.total());
+        assert_eq!(vec!["appear cat 2", "appear dog 3", "remove cat 2", "remove dog 3", "appear eel 1"], *log.borrow());
+    }
+
+    #[test]
+    fn TEST_ObservableFrequencyMap_into_inner() {
+        let log = Log::default();
+        let mut ofm = observed(&log);
+
+        ofm.push("cat");
+
+        let fm = ofm.into_inner();
+
+        assert_eq!(1, fm.total());
+        assert_eq!(1, fm["cat"]);
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
@@ -0,0 +1,356 @@
+// containers/pruning.rs : `PruneSummary`
+
+use super::{
+    FrequencyMap,
+    UnicodePointMap,
+};
+
+use alloc::vec::Vec;
+
+use core::{
+    cmp as std_cmp,
+    hash as std_hash,
+};
+
+
+/// A summary of the records removed by a pruning operation, such as
+/// `prune_below()` or `keep_top()`.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub struct PruneSummary {
+    /// The number of records removed.
+    pub records_removed : usize,
+    /// The total frequency count carried by the records removed.
+    pub total_removed :   i64,
+}
+
+// Implementation
+
+impl PruneSummary {
+    #[inline]
+    fn record_(
+        &mut self,
+        count : isize,
+    ) {
+        self.records_removed += 1;
+        self.total_removed += count as i64;
+    }
+}
+
+/// Obtains the count of the `n`th-highest record, and the number of records
+/// whose count exceeds it, or `None` if there are no more than `n` records.
+fn top_n_boundary_<I>(
+    counts : I,
+    n : usize,
+) -> Option<(isize, usize)>
+where
+    I : Iterator<Item = isize>,
+{
+    let mut counts : Vec<isize> = counts.collect();
+
+    if n >= counts.len() {
+        return None;
+    }
+
+    if 0 == n {
+        return Some((isize::MAX, 0));
+    }
+
+    let (_, &mut boundary, _) = counts.select_nth_unstable_by(n - 1, |l, r| r.cmp(l));
+    let above = counts.iter().filter(|&&count| count > boundary).count();
+
+    Some((boundary, above))
+}
+
+
+// FrequencyMap
+
+impl<K> FrequencyMap<K> {
+    /// Retains only the `n` records with the highest counts, removing all
+    /// others. Where several records share the count at the boundary, those
+    /// retained are chosen arbitrarily.
+    pub fn keep_top(
+        &mut self,
+        n : usize,
+    ) -> PruneSummary {
+        match top_n_boundary_(self.iter().map(|(_, &count)| count), n) {
+            Some((boundary, above)) => {
+                let mut at_boundary = n - above;
+
+                self.prune_if(|_, count| {
+                    if count > boundary {
+                        false
+                    } else if count == boundary && 0 != at_boundary {
+                        at_boundary -= 1;
+
+                        false
+                    } else {
+                        true
+                    }
+                })
+            },
+            None => PruneSummary::default(),
+        }
+    }
+
+    /// Removes all records with a count greater than `max_count`.
+    #[inline]
+    pub fn prune_above(
+        &mut self,
+        max_count : isize,
+    ) -> PruneSummary {
+        self.prune_if(|_, count| count > max_count)
+    }
+
+    /// Removes all records with a count less than `min_count`.
+    #[inline]
+    pub fn prune_below(
+        &mut self,
+        min_count : isize,
+    ) -> PruneSummary {
+        self.prune_if(|_, count| count < min_count)
+    }
+
+    /// Removes all records for which the predicate returns `true`.
+    pub fn prune_if<F>(
+        &mut self,
+        mut f : F,
+    ) -> PruneSummary
+    where
+        F : FnMut(&K, isize) -> bool,
+    {
+        let mut summary = PruneSummary::default();
+
+        self.retain(|key, count| {
+            if f(key, count) {
+                summary.record_(count);
+
+                false
+            } else {
+                true
+            }
+        });
+
+        summary
+    }
+}
+
+impl<K : Clone + std_cmp::Eq + std_hash::Hash> FrequencyMap<K> {
+    /// Obtains a new instance containing only the records for which the
+    /// predicate returns `true`.
+    pub fn filtered<F>(
+        &self,
+        mut f : F,
+    ) -> Self
+    where
+        F : FnMut(&K, isize) -> bool,
+    {
+        let mut fm = Self::new();
+
+        for (key, &count) in self.iter() {
+            if f(key, count) {
+                fm.insert(key.clone(), count);
+            }
+        }
+
+        fm
+    }
+}
+
+
+// UnicodePointMap
+
+impl UnicodePointMap {
+    /// Obtains a new instance, with the same contiguous ceiling, containing
+    /// only the records for which the predicate returns `true`.
+    pub fn filtered<F>(
+        &self,
+        mut f : F,
+    ) -> Self
+    where
+        F : FnMut(char, isize) -> bool,
+    {
+        let mut upm = self.empty_like_();
+
+        for (c, count) in self.iter() {
+            if f(c, count) {
+                upm.insert(c, count);
+            }
+        }
+
+        upm
+    }
+
+    /// Retains only the `n` records with the highest counts, removing all
+    /// others. Where several records share the count at the boundary, those
+    /// retained are chosen arbitrarily.
+    pub fn keep_top(
+        &mut self,
+        n : usize,
+    ) -> PruneSummary {
+        match top_n_boundary_(self.iter().map(|(_, count)| count), n) {
+            Some((boundary, above)) => {
+                let mut at_boundary = n - above;
+
+                self.prune_if(|_, count| {
+                    if count > boundary {
+                        false
+                    } else if count == boundary && 0 != at_boundary {
+                        at_boundary -= 1;
+
+                        false
+                    } else {
+                        true
+                    }
+                })
+            },
+            None => PruneSummary::default(),
+        }
+    }
+
+    /// Removes all records with a count greater than `max_count`.
+    #[inline]
+    pub fn prune_above(
+        &mut self,
+        max_count : isize,
+    ) -> PruneSummary {
+        self.prune_if(|_, count| count > max_count)
+    }
+
+    /// Removes all records with a count less than `min_count`.
+    #[inline]
+    pub fn prune_below(
+        &mut self,
+        min_count : isize,
+    ) -> PruneSummary {
+        self.prune_if(|_, count| count < min_count)
+    }
+
+    /// Removes all records for which the predicate returns `true`.
+    pub fn prune_if<F>(
+        &mut self,
+        mut f : F,
+    ) -> PruneSummary
+    where
+        F : FnMut(char, isize) -> bool,
+    {
+        let mut summary = PruneSummary::default();
+
+        self.retain(|c, count| {
+            if f(c, count) {
+                summary.record_(count);
+
+                false
+            } else {
+                true
+            }
+        });
+
+        summary
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::PruneSummary;
+
+    use crate::containers::{
+        FrequencyMap,
+        UnicodePointMap,
+    };
+
+
+    fn make_fm() -> FrequencyMap<&'static str> {
+        FrequencyMap::from([
+            // insert list
+            ("a", 1),
+            ("b", 2),
+            ("c", 3),
+            ("d", 3),
+            ("e", 5),
+            ("f", 8),
+        ])
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_prune_below_AND_prune_above() {
+        let mut fm = make_fm();
+
+        assert_eq!(PruneSummary { records_removed : 2, total_removed : 3 }, fm.prune_below(3));
+        assert_eq!(4, fm.len());
+        assert_eq!(19, fm.total());
+
+        assert_eq!(PruneSummary { records_removed : 1, total_removed : 8 }, fm.prune_above(5));
+        assert_eq!(3, fm.len());
+        assert_eq!(11, fm.total());
+
+        assert_eq!(PruneSummary::default(), fm.prune_above(5));
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_keep_top() {
+        {
+            let mut fm = make_fm();
+
+            assert_eq!(PruneSummary { records_removed : 3, total_removed : 6 }, fm.keep_top(3));
+            assert_eq!(3, fm.len());
+            assert_eq!(16, fm.total());
+            assert!(fm.contains_key("e"));
+            assert!(fm.contains_key("f"));
+            assert!(fm.contains_key("c") != fm.contains_key("d"));
+        }
+
+        {
+            let mut fm = make_fm();
+
+            assert_eq!(PruneSummary::default(), fm.keep_top(6));
+            assert_eq!(6, fm.len());
+
+            assert_eq!(PruneSummary { records_removed : 6, total_removed : 22 }, fm.keep_top(0));
+            assert!(fm.is_empty());
+            assert_eq!(0, fm.total());
+        }
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_filtered() {
+        let fm = make_fm();
+
+        let odd = fm.filtered(|_, count| 0 != count % 2);
+
+        assert_eq!(4, odd.len());
+        assert_eq!(12, odd.total());
+        assert!(!odd.contains_key("b"));
+        assert_eq!(6, fm.len());
+    }
+
+    #[test]
+    fn TEST_UnicodePointMap_PRUNING() {
+        let mut upm = UnicodePointMap::from_iter("mississippi🐻🐻".chars());
+
+        let vowels = upm.filtered(|c, _| "aeiou".contains(c));
+
+        assert_eq!(1, vowels.len());
+        assert_eq!(4, vowels.total());
+
+        assert_eq!(PruneSummary { records_removed : 1, total_removed : 1 }, upm.prune_below(2));
+        assert_eq!(PruneSummary { records_removed : 2, total_removed : 8 }, upm.prune_above(3));
+        assert_eq!(2, upm.len());
+        assert_eq!(4, upm.total());
+
+        assert_eq!(PruneSummary { records_removed : 1, total_removed : 2 }, upm.keep_top(1));
+        assert_eq!(1, upm.len());
+        assert_eq!(2, upm.total());
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
@@ -0,0 +1,540 @@
+// containers/ranking.rs : `RankIndex`
+
+use super::{
+    FrequencyMap,
+    UnicodePointMap,
+};
+
+use crate::hash::HashMap;
+
+use base_traits::{
+    IsEmpty,
+    Len,
+};
+
+use alloc::vec::Vec;
+
+use core::{
+    borrow as std_borrow,
+    cmp as std_cmp,
+    hash as std_hash,
+};
+
+
+/// A precomputed index of the records of a [`FrequencyMap`] or
+/// [`UnicodePointMap`], ordered by descending count, against which rank
+/// and percentile queries may be made repeatedly without re-examining
+/// every record.
+///
+/// The index is a snapshot, and does not reflect subsequent changes to the
+/// map from which it was built.
+///
+/// Ranks and percentiles are defined, and ties handled, as described for
+/// [`FrequencyMap::rank_of()`], [`FrequencyMap::percentile_of()`],
+/// [`FrequencyMap::key_at_rank()`], and [`FrequencyMap::count_quantile()`],
+/// which give identical results.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct RankIndex<K> {
+    /// The records, by descending count and then by ascending key.
+    records : Vec<(K, isize)>,
+    /// The count of each key.
+    counts :  HashMap<K, isize>,
+}
+
+// API functions
+
+impl<K> RankIndex<K>
+where
+    K : Clone + std_cmp::Ord + std_hash::Hash,
+{
+    /// Creates an instance from the given records, which must have unique
+    /// keys and non-zero counts.
+    fn from_records_(mut records : Vec<(K, isize)>) -> Self {
+        records.sort_unstable_by(|(lhs_key, lhs_count), (rhs_key, rhs_count)| rhs_count.cmp(lhs_count).then_with(|| lhs_key.cmp(rhs_key)));
+
+        let counts = records.iter().cloned().collect();
+
+        Self {
+            records,
+            counts,
+        }
+    }
+}
+
+// Non-mutating methods
+
+impl<K> RankIndex<K>
+where
+    K : std_cmp::Eq + std_hash::Hash,
+{
+    /// Obtains the count at the `q`th quantile of the counts of the records,
+    /// as [`FrequencyMap::count_quantile()`].
+    pub fn count_quantile(
+        &self,
+        q : f64,
+    ) -> Option<isize> {
+        let ix = nearest_rank_index_(q, self.records.len())?;
+
+        // `self.records` is in descending order of count
+        Some(self.records[self.records.len() - 1 - ix].1)
+    }
+
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// An iterator visiting all records in rank order, i.e. by descending
+    /// count and then by ascending key. The iterator element type is
+    /// `(&'a K, isize)`.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, isize)> {
+        self.records.iter().map(|(key, count)| (key, *count))
+    }
+
+    /// Obtains the key at the 1-based `rank`, as
+    /// [`FrequencyMap::key_at_rank()`].
+    pub fn key_at_rank(
+        &self,
+        rank : usize,
+    ) -> Option<&K> {
+        rank.checked_sub(1).and_then(|ix| self.records.get(ix)).map(|(key, _)| key)
+    }
+
+    /// Obtains the number of records.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Obtains the percentile of `key`, as
+    /// [`FrequencyMap::percentile_of()`].
+    pub fn percentile_of<Q>(
+        &self,
+        key : &Q,
+    ) -> Option<f64>
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        let (greater, equal) = self.greater_and_equal_(key)?;
+
+        Some(percentile_(greater, equal, self.records.len()))
+    }
+
+    /// Obtains the 1-based rank of `key`, as [`FrequencyMap::rank_of()`].
+    pub fn rank_of<Q>(
+        &self,
+        key : &Q,
+    ) -> Option<usize>
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        self.greater_and_equal_(key).map(|(greater, _)| 1 + greater)
+    }
+}
+
+// Implementation
+
+impl<K> RankIndex<K>
+where
+    K : std_cmp::Eq + std_hash::Hash,
+{
+    /// Obtains the number of records whose count is greater than, and
+    /// equal to, that of `key`, or `None` if there is no record for `key`.
+    fn greater_and_equal_<Q>(
+        &self,
+        key : &Q,
+    ) -> Option<(usize, usize)>
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        let count = *self.counts.get(key)?;
+
+        let greater = self.records.partition_point(|(_, c)| *c > count);
+        let greater_or_equal = self.records.partition_point(|(_, c)| *c >= count);
+
+        Some((greater, greater_or_equal - greater))
+    }
+}
+
+// Trait implementations
+
+impl<K> IsEmpty for RankIndex<K> {
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+impl<K> Len for RankIndex<K> {
+    /// Obtains the number of records.
+    #[inline]
+    fn len(&self) -> usize {
+        self.records.len()
+    }
+}
+
+
+/// Obtains the number of the given counts that are greater than, and
+/// equal to, `count`, along with the total number of counts.
+fn greater_equal_total_<I>(
+    counts : I,
+    count : isize,
+) -> (usize, usize, usize)
+where
+    I : Iterator<Item = isize>,
+{
+    counts.fold((0, 0, 0), |(greater, equal, total), c| {
+        match c.cmp(&count) {
+            std_cmp::Ordering::Greater => (greater + 1, equal, total + 1),
+            std_cmp::Ordering::Equal => (greater, equal + 1, total + 1),
+            std_cmp::Ordering::Less => (greater, equal, total + 1),
+        }
+    })
+}
+
+/// Obtains the mid-rank percentile of a record, given the numbers of
+/// records whose counts are greater than and equal to its own, out of
+/// `total` records.
+fn percentile_(
+    greater : usize,
+    equal : usize,
+    total : usize,
+) -> f64 {
+    let less = total - greater - equal;
+
+    100.0 * (less as f64 + 0.5 * equal as f64) / total as f64
+}
+
+/// Obtains the 0-based index, into `n` ascending counts, of the count at
+/// the `q`th quantile according to the nearest-rank method, or `None` if
+/// `n` is 0 or `q` is not in the range [0, 1].
+fn nearest_rank_index_(
+    q : f64,
+    n : usize,
+) -> Option<usize> {
+    if 0 == n || !(0.0..=1.0).contains(&q) {
+        return None;
+    }
+
+    let x = q * n as f64;
+    let mut rank = x as usize;
+
+    if (rank as f64) < x {
+        rank += 1;
+    }
+
+    Some(rank.clamp(1, n) - 1)
+}
+
+/// Obtains the count at the `q`th quantile of `counts`, according to the
+/// nearest-rank method.
+fn count_quantile_(
+    mut counts : Vec<isize>,
+    q : f64,
+) -> Option<isize> {
+    let ix = nearest_rank_index_(q, counts.len())?;
+
+    Some(*counts.select_nth_unstable(ix).1)
+}
+
+
+// FrequencyMap
+
+impl<K> FrequencyMap<K> {
+    /// Obtains the count at the `q`th quantile, for `q` in the range
+    /// [0, 1], of the counts of the records, or `None` if the instance is
+    /// empty or `q` is out of range.
+    ///
+    /// The quantile is determined by the nearest-rank method, i.e. it is
+    /// the smallest count such that at least a fraction `q` of the records
+    /// have that count or less. Hence `count_quantile(0.0)` obtains the
+    /// lowest count, and `count_quantile(1.0)` the highest.
+    pub fn count_quantile(
+        &self,
+        q : f64,
+    ) -> Option<isize> {
+        count_quantile_(self.iter().map(|(_, &count)| count).collect(), q)
+    }
+}
+
+impl<K : std_cmp::Eq + std_hash::Hash> FrequencyMap<K> {
+    /// Obtains the percentile, in the range (0, 100), of `key` among the
+    /// records by their counts, or `None` if there is no record for `key`.
+    ///
+    /// The percentile is the mid-rank percentile, i.e. the percentage of
+    /// records that have a lower count plus half the percentage that have
+    /// the same count (including `key` itself), so tied keys share a
+    /// percentile. For example, a key is in the top 1% of a map of 1,000
+    /// distinct counts if its percentile is at least 99.
+    pub fn percentile_of<Q>(
+        &self,
+        key : &Q,
+    ) -> Option<f64>
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        let count = self.get(key);
+
+        if 0 == count {
+            return None;
+        }
+
+        let (greater, equal, total) = greater_equal_total_(self.iter().map(|(_, &c)| c), count);
+
+        Some(percentile_(greater, equal, total))
+    }
+
+    /// Obtains the 1-based rank of `key` among the records, by descending
+    /// count, or `None` if there is no record for `key`.
+    ///
+    /// Tied keys share the same rank, being 1 more than the number of
+    /// records with a greater count (i.e. "standard competition" ranking,
+    /// as in 1, 2, 2, 4), so the highest rank may be less than `#len()`.
+    pub fn rank_of<Q>(
+        &self,
+        key : &Q,
+    ) -> Option<usize>
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        let count = self.get(key);
+
+        if 0 == count {
+            return None;
+        }
+
+        Some(1 + self.iter().filter(|(_, &c)| c > count).count())
+    }
+}
+
+impl<K : std_cmp::Ord + std_hash::Hash> FrequencyMap<K> {
+    /// Obtains the key at the 1-based position `rank` when the records are
+    /// ordered by descending count, or `None` if `rank` is 0 or greater
+    /// than `#len()`.
+    ///
+    /// Ties are broken by ascending key, so that the result is
+    /// deterministic. Where several keys share a count, the first of them
+    /// is at the position given by [`#rank_of()`](Self::rank_of), and the
+    /// others are at the positions that follow.
+    pub fn key_at_rank(
+        &self,
+        rank : usize,
+    ) -> Option<&K> {
+        let ix = rank.checked_sub(1)?;
+
+        let mut records : Vec<_> = self.iter().collect();
+
+        if ix >= records.len() {
+            return None;
+        }
+
+        let (_, (key, _), _) = records.select_nth_unstable_by(ix, |(lhs_key, lhs_count), (rhs_key, rhs_count)| rhs_count.cmp(lhs_count).then_with(|| lhs_key.cmp(rhs_key)));
+
+        Some(*key)
+    }
+}
+
+impl<K : Clone + std_cmp::Ord + std_hash::Hash> FrequencyMap<K> {
+    /// Obtains a [`RankIndex`] of the records, for repeated rank and
+    /// percentile queries.
+    pub fn rank_index(&self) -> RankIndex<K> {
+        RankIndex::from_records_(self.iter().map(|(key, &count)| (key.clone(), count)).collect())
+    }
+}
+
+
+// UnicodePointMap
+
+impl UnicodePointMap {
+    /// Obtains the count at the `q`th quantile of the counts of the
+    /// records, as [`FrequencyMap::count_quantile()`].
+    pub fn count_quantile(
+        &self,
+        q : f64,
+    ) -> Option<isize> {
+        count_quantile_(self.iter().map(|(_, count)| count).collect(), q)
+    }
+
+    /// Obtains the code point at the 1-based position `rank` when the
+    /// records are ordered by descending count, with ties broken by
+    /// ascending code point, as [`FrequencyMap::key_at_rank()`].
+    pub fn key_at_rank(
+        &self,
+        rank : usize,
+    ) -> Option<char> {
+        let ix = rank.checked_sub(1)?;
+
+        let mut records : Vec<_> = self.iter().collect();
+
+        if ix >= records.len() {
+            return None;
+        }
+
+        let (_, (c, _), _) = records.select_nth_unstable_by(ix, |(lhs_c, lhs_count), (rhs_c, rhs_count)| rhs_count.cmp(lhs_count).then_with(|| lhs_c.cmp(rhs_c)));
+
+        Some(*c)
+    }
+
+    /// Obtains the percentile of `c` among the records by their counts, as
+    /// [`FrequencyMap::percentile_of()`].
+    pub fn percentile_of(
+        &self,
+        c : &char,
+    ) -> Option<f64> {
+        let count = self.get(c);
+
+        if 0 == count {
+            return None;
+        }
+
+        let (greater, equal, total) = greater_equal_total_(self.iter().map(|(_, count)| count), count);
+
+        Some(percentile_(greater, equal, total))
+    }
+
+    /// Obtains a [`RankIndex`] of the records, for repeated rank and
+    /// percentile queries.
+    pub fn rank_index(&self) -> RankIndex<char> {
+        RankIndex::from_records_(self.iter().collect())
+    }
+
+    /// Obtains the 1-based rank of `c` among the records, by descending
+    /// count, as [`FrequencyMap::rank_of()`].
+    pub fn rank_of(
+        &self,
+        c : &char,
+    ) -> Option<usize> {
+        let count = self.get(c);
+
+        if 0 == count {
+            return None;
+        }
+
+        Some(1 + self.iter().filter(|&(_, n)| n > count).count())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use crate::containers::{
+        FrequencyMap,
+        UnicodePointMap,
+    };
+
+
+    fn make_fm() -> FrequencyMap<&'static str> {
+        FrequencyMap::from([
+            // insert list
+            ("a", 10),
+            ("b", 7),
+            ("c", 7),
+            ("d", 3),
+            ("e", 1),
+        ])
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_rank_of_AND_key_at_rank() {
+        let fm = make_fm();
+
+        assert_eq!(Some(1), fm.rank_of("a"));
+        assert_eq!(Some(2), fm.rank_of("b"));
+        assert_eq!(Some(2), fm.rank_of("c"));
+        assert_eq!(Some(4), fm.rank_of("d"));
+        assert_eq!(Some(5), fm.rank_of("e"));
+        assert_eq!(None, fm.rank_of("f"));
+
+        assert_eq!(None, fm.key_at_rank(0));
+        assert_eq!(Some(&"a"), fm.key_at_rank(1));
+        assert_eq!(Some(&"b"), fm.key_at_rank(2));
+        assert_eq!(Some(&"c"), fm.key_at_rank(3));
+        assert_eq!(Some(&"e"), fm.key_at_rank(5));
+        assert_eq!(None, fm.key_at_rank(6));
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_percentile_of_AND_count_quantile() {
+        let fm = make_fm();
+
+        assert_eq!(Some(90.0), fm.percentile_of("a"));
+        assert_eq!(Some(60.0), fm.percentile_of("b"));
+        assert_eq!(Some(60.0), fm.percentile_of("c"));
+        assert_eq!(Some(10.0), fm.percentile_of("e"));
+        assert_eq!(None, fm.percentile_of("f"));
+
+        assert_eq!(Some(1), fm.count_quantile(0.0));
+        assert_eq!(Some(1), fm.count_quantile(0.2));
+        assert_eq!(Some(3), fm.count_quantile(0.21));
+        assert_eq!(Some(7), fm.count_quantile(0.5));
+        assert_eq!(Some(10), fm.count_quantile(1.0));
+        assert_eq!(None, fm.count_quantile(1.5));
+        assert_eq!(None, fm.count_quantile(f64::NAN));
+        assert_eq!(None, FrequencyMap::<i32>::new().count_quantile(0.5));
+    }
+
+    #[test]
+    fn TEST_RankIndex_MATCHES_FrequencyMap() {
+        let fm = FrequencyMap::<i32>::from_iter((0..200).map(|n| n % 37 * (n % 11)));
+        let index = fm.rank_index();
+
+        assert_eq!(fm.len(), index.len());
+
+        for (key, _) in fm.iter() {
+            assert_eq!(fm.rank_of(key), index.rank_of(key));
+            assert_eq!(fm.percentile_of(key), index.percentile_of(key));
+        }
+
+        for rank in 0..=fm.len() + 1 {
+            assert_eq!(fm.key_at_rank(rank), index.key_at_rank(rank));
+        }
+
+        for q in [0.0, 0.01, 0.25, 0.5, 0.75, 0.99, 1.0] {
+            assert_eq!(fm.count_quantile(q), index.count_quantile(q));
+        }
+
+        assert_eq!(None, index.rank_of(&-1));
+
+        let counts : Vec<_> = index.iter().map(|(_, count)| count).collect();
+
+        assert!(counts.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn TEST_UnicodePointMap_ranking() {
+        let upm = UnicodePointMap::from_iter("mississippi".chars());
+
+        assert_eq!(Some(1), upm.rank_of(&'i'));
+        assert_eq!(Some(1), upm.rank_of(&'s'));
+        assert_eq!(Some(3), upm.rank_of(&'p'));
+        assert_eq!(Some(4), upm.rank_of(&'m'));
+        assert_eq!(None, upm.rank_of(&'x'));
+
+        assert_eq!(Some('i'), upm.key_at_rank(1));
+        assert_eq!(Some('s'), upm.key_at_rank(2));
+        assert_eq!(Some('m'), upm.key_at_rank(4));
+
+        assert_eq!(Some(75.0), upm.percentile_of(&'s'));
+        assert_eq!(Some(4), upm.count_quantile(0.9));
+
+        let index = upm.rank_index();
+
+        assert_eq!(Some(3), index.rank_of(&'p'));
+        assert_eq!(Some(&'s'), index.key_at_rank(2));
+        assert_eq!(Some(12.5), index.percentile_of(&'m'));
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
@@ -0,0 +1,210 @@
+// containers/rollup.rs : `rollup()` and `rollup_levels()`
+
+use super::{
+    FrequencyMap,
+    UnicodePointMap,
+};
+
+use alloc::vec::Vec;
+
+use core::{
+    cmp as std_cmp,
+    hash as std_hash,
+};
+
+
+// FrequencyMap
+
+impl<K> FrequencyMap<K> {
+    /// Obtains a new instance whose records are the groups obtained by
+    /// applying `project` to each key, with the counts of the keys in each
+    /// group summed.
+    ///
+    /// A group whose sum is 0 is omitted.
+    pub fn rollup<G, F>(
+        &self,
+        project : F,
+    ) -> FrequencyMap<G>
+    where
+        G : std_cmp::Eq + std_hash::Hash,
+        F : Fn(&K) -> G,
+    {
+        let mut fm = FrequencyMap::new();
+
+        for (key, &count) in self.iter() {
+            fm.push_n(project(key), count);
+        }
+
+        fm
+    }
+
+    /// Obtains, in a single pass, one rolled-up instance per element of
+    /// `projections`, as if by calling [`#rollup()`](Self::rollup) with
+    /// each in turn.
+    ///
+    /// This is intended for hierarchical groupings, such as path, then
+    /// directory, then top-level directory.
+    pub fn rollup_levels<G>(
+        &self,
+        projections : &[&dyn Fn(&K) -> G],
+    ) -> Vec<FrequencyMap<G>>
+    where
+        G : std_cmp::Eq + std_hash::Hash,
+    {
+        let mut levels : Vec<_> = projections.iter().map(|_| FrequencyMap::new()).collect();
+
+        for (key, &count) in self.iter() {
+            for (fm, project) in levels.iter_mut().zip(projections) {
+                fm.push_n(project(key), count);
+            }
+        }
+
+        levels
+    }
+}
+
+
+// UnicodePointMap
+
+impl UnicodePointMap {
+    /// Obtains a [`FrequencyMap`] whose records are the groups obtained by
+    /// applying `project` to each code point, with the counts of the code
+    /// points in each group summed.
+    ///
+    /// A group whose sum is 0 is omitted.
+    pub fn rollup<G, F>(
+        &self,
+        project : F,
+    ) -> FrequencyMap<G>
+    where
+        G : std_cmp::Eq + std_hash::Hash,
+        F : Fn(char) -> G,
+    {
+        let mut fm = FrequencyMap::new();
+
+        for (c, count) in self.iter() {
+            fm.push_n(project(c), count);
+        }
+
+        fm
+    }
+
+    /// Obtains, in a single pass, one rolled-up [`FrequencyMap`] per
+    /// element of `projections`, as if by calling
+    /// [`#rollup()`](Self::rollup) with each in turn.
+    pub fn rollup_levels<G>(
+        &self,
+        projections : &[&dyn Fn(char) -> G],
+    ) -> Vec<FrequencyMap<G>>
+    where
+        G : std_cmp::Eq + std_hash::Hash,
+    {
+        let mut levels : Vec<_> = projections.iter().map(|_| FrequencyMap::new()).collect();
+
+        for (c, count) in self.iter() {
+            for (fm, project) in levels.iter_mut().zip(projections) {
+                fm.push_n(project(c), count);
+            }
+        }
+
+        levels
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use crate::containers::{
+        FrequencyMap,
+        UnicodePointMap,
+    };
+
+
+    fn parent(path : &str) -> &str {
+        path.rsplit_once('/').map_or("", |(dir, _)| dir)
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_rollup_1() {
+        let fm = FrequencyMap::<i32>::from([
+            // insert list
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, -3),
+            (5, 1),
+        ]);
+
+        let parity = fm.rollup(|n| n % 2);
+
+        assert_eq!(1, parity.len());
+        assert_eq!(7, parity[&1]);
+        assert!(!parity.contains_key(&0));
+        assert_eq!(fm.total(), parity.total());
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_rollup_levels_1() {
+        let fm = FrequencyMap::<&str>::from([
+            // insert list
+            ("src/containers/frequency_map.rs", 5),
+            ("src/containers/mod.rs", 1),
+            ("src/lib.rs", 2),
+            ("benches/frequency-map.rs", 3),
+        ]);
+
+        let levels = fm.rollup_levels(&[
+            // projections
+            &|path : &&str| parent(path),
+            &|path : &&str| path.split_once('/').map_or("", |(top, _)| top),
+        ]);
+
+        assert_eq!(2, levels.len());
+
+        assert_eq!(3, levels[0].len());
+        assert_eq!(6, levels[0]["src/containers"]);
+        assert_eq!(2, levels[0]["src"]);
+        assert_eq!(3, levels[0]["benches"]);
+
+        assert_eq!(2, levels[1].len());
+        assert_eq!(8, levels[1]["src"]);
+        assert_eq!(3, levels[1]["benches"]);
+        assert_eq!(fm.total(), levels[1].total());
+    }
+
+    #[test]
+    fn TEST_UnicodePointMap_rollup_1() {
+        let upm = UnicodePointMap::from_iter("Hello, World! 42".chars());
+
+        let classes = upm.rollup(|c| {
+            if c.is_alphabetic() {
+                "alpha"
+            } else if c.is_numeric() {
+                "digit"
+            } else {
+                "other"
+            }
+        });
+
+        assert_eq!(10, classes["alpha"]);
+        assert_eq!(2, classes["digit"]);
+        assert_eq!(4, classes["other"]);
+
+        let levels = upm.rollup_levels(&[
+            // projections
+            &|c : char| c.is_uppercase(),
+            &|c : char| c.is_ascii(),
+        ]);
+
+        assert_eq!(2, levels[0][&true]);
+        assert_eq!(14, levels[0][&false]);
+        assert_eq!(16, levels[1][&true]);
+        assert!(!levels[1].contains_key(&false));
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
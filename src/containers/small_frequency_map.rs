@@ -0,0 +1,538 @@
+// containers/small_frequency_map.rs : `SmallFrequencyMap`
+
+use super::FrequencyMap;
+
+use crate::hash::hash_map;
+
+use base_traits::{
+    IsEmpty,
+    Len,
+};
+
+use core::{
+    borrow as std_borrow,
+    cmp as std_cmp,
+    hash as std_hash,
+    ops as std_ops,
+    slice as std_slice,
+};
+
+
+/// The storage of a [`SmallFrequencyMap`].
+#[derive(Clone)]
+#[derive(Debug)]
+enum Storage<K, const N : usize> {
+    /// Up to `N` records, held in the first `len` elements of `records`.
+    Inline {
+        records : [Option<(K, isize)>; N],
+        len :     usize,
+    },
+    /// Records that have spilled into a [`FrequencyMap`].
+    Spilled(FrequencyMap<K>),
+}
+
+
+/// A container that measures the frequencies of the unique elements it
+/// contains, holding up to `N` records inline and searching them linearly,
+/// and spilling into a [`FrequencyMap`] when it grows beyond that.
+///
+/// For maps that hold only a handful of distinct keys this avoids both the
+/// allocation and the hashing of a [`FrequencyMap`]. Once spilled, an
+/// instance remains spilled until [`#clear()`](Self::clear) is called.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct SmallFrequencyMap<K, const N : usize> {
+    /// The records.
+    storage : Storage<K, N>,
+    /// The total number of keys represented.
+    total :   isize,
+}
+
+// API functions
+
+impl<K, const N : usize> SmallFrequencyMap<K, N> {
+    /// Creates a new empty instance.
+    pub fn new() -> Self {
+        Self {
+            storage : Self::empty_storage_(),
+            total :   0,
+        }
+    }
+}
+
+// Mutating methods
+
+impl<K, const N : usize> SmallFrequencyMap<K, N> {
+    /// Clears the map, removing all records, resetting `#total()`, and
+    /// returning to inline storage.
+    pub fn clear(&mut self) {
+        self.storage = Self::empty_storage_();
+        self.total = 0;
+    }
+}
+
+impl<K : std_cmp::Eq + std_hash::Hash, const N : usize> SmallFrequencyMap<K, N> {
+    /// Inserts a record with the given `key` and `count`, replacing any
+    /// existing record with that `key`. In the case that `count` is 0 any
+    /// existing record is removed and no record is created.
+    pub fn insert(
+        &mut self,
+        key : K,
+        count : isize,
+    ) -> Option<isize> {
+        let prev = self.remove(&key);
+
+        self.push_n(key, count);
+
+        prev
+    }
+
+    /// Updates the count by 1 of an existing record identified by `key`, or
+    /// creates, with a count of 1, a new record.
+    #[inline]
+    pub fn push(
+        &mut self,
+        key : K,
+    ) {
+        self.push_n(key, 1);
+    }
+
+    /// Updates the count by `count` of an existing record identifed by
+    /// `key`, or creates, with the given `count`, a new record.
+    ///
+    /// In the case that the resulting count of an existing record is 0 then
+    /// the record is removed.
+    pub fn push_n(
+        &mut self,
+        key : K,
+        count : isize,
+    ) {
+        if 0 == count {
+            return;
+        }
+
+        self.total += count;
+
+        match &mut self.storage {
+            Storage::Inline { records, len } => {
+                if let Some(ix) = Self::position_(&records[.. *len], &key) {
+                    if let Some((_, v)) = &mut records[ix] {
+                        *v += count;
+
+                        if 0 == *v {
+                            Self::swap_remove_(records, len, ix);
+                        }
+                    }
+                } else if *len < N {
+                    records[*len] = Some((key, count));
+
+                    *len += 1;
+                } else {
+                    let mut fm = FrequencyMap::with_capacity(2 * N);
+
+                    for (k, v) in records.iter_mut().filter_map(Option::take) {
+                        fm.insert(k, v);
+                    }
+
+                    fm.insert(key, count);
+
+                    self.storage = Storage::Spilled(fm);
+                }
+            },
+            Storage::Spilled(fm) => fm.push_n(key, count),
+        }
+    }
+
+    /// Removes a key from the map, returning the count of the key if the
+    /// key was previously in the map.
+    pub fn remove<Q>(
+        &mut self,
+        key : &Q,
+    ) -> Option<isize>
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        let r = match &mut self.storage {
+            Storage::Inline { records, len } => {
+                let ix = Self::position_(&records[.. *len], key)?;
+
+                Self::swap_remove_(records, len, ix)
+            },
+            Storage::Spilled(fm) => fm.remove(key),
+        };
+
+        if let Some(v) = r {
+            self.total -= v;
+        }
+
+        r
+    }
+}
+
+// Non-mutating methods
+
+impl<K : std_cmp::Eq + std_hash::Hash, const N : usize> SmallFrequencyMap<K, N> {
+    /// Indicates whether a record exists for the given `key`.
+    #[inline]
+    pub fn contains_key<Q>(
+        &self,
+        key : &Q,
+    ) -> bool
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        0 != *self.get_(key)
+    }
+
+    /// Obtains the count corresponding to the given `key`, obtaining 0 in
+    /// the case that no such record exists.
+    #[inline]
+    pub fn get<Q>(
+        &self,
+        key : &Q,
+    ) -> isize
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        *self.get_(key)
+    }
+}
+
+impl<K, const N : usize> SmallFrequencyMap<K, N> {
+    /// Indicates whether the records have spilled from inline storage into
+    /// a [`FrequencyMap`].
+    #[inline]
+    pub fn is_spilled(&self) -> bool {
+        matches!(self.storage, Storage::Spilled(_))
+    }
+
+    /// An iterator visiting all key-count pairs in arbitrary order. The
+    /// iterator element type is `(&'a K, &'a isize)`.
+    pub fn iter(&self) -> SmallFrequencyMapIter<'_, K> {
+        let inner = match &self.storage {
+            Storage::Inline { records, len } => IterInner::Inline(records[.. *len].iter()),
+            Storage::Spilled(fm) => IterInner::Spilled(fm.iter()),
+        };
+
+        SmallFrequencyMapIter {
+            inner,
+        }
+    }
+
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.is_empty_()
+    }
+
+    /// Obtains the number of records.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len_()
+    }
+
+    /// Indicates the total frequency count across all records.
+    #[inline]
+    pub fn total(&self) -> isize {
+        self.total
+    }
+}
+
+// Iteration
+
+#[derive(Clone)]
+#[derive(Debug)]
+enum IterInner<'a, K> {
+    Inline(std_slice::Iter<'a, Option<(K, isize)>>),
+    Spilled(hash_map::Iter<'a, K, isize>),
+}
+
+/// An iterator over the records of a [`SmallFrequencyMap`], obtained from
+/// [`SmallFrequencyMap::iter()`].
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct SmallFrequencyMapIter<'a, K> {
+    inner : IterInner<'a, K>,
+}
+
+impl<'a, K> Iterator for SmallFrequencyMapIter<'a, K> {
+    type Item = (&'a K, &'a isize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            IterInner::Inline(records) => {
+                records.next().and_then(Option::as_ref).map(|(key, count)| (key, count))
+            },
+            IterInner::Spilled(iter) => iter.next(),
+        }
+    }
+}
+
+// Implementation
+
+impl<K, const N : usize> SmallFrequencyMap<K, N> {
+    fn empty_storage_() -> Storage<K, N> {
+        Storage::Inline {
+            records : core::array::from_fn(|_| None),
+            len :     0,
+        }
+    }
+
+    #[inline]
+    fn is_empty_(&self) -> bool {
+        0 == self.len_()
+    }
+
+    fn len_(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { len, .. } => *len,
+            Storage::Spilled(fm) => fm.len(),
+        }
+    }
+
+    fn swap_remove_(
+        records : &mut [Option<(K, isize)>; N],
+        len : &mut usize,
+        ix : usize,
+    ) -> Option<isize> {
+        *len -= 1;
+
+        records.swap(ix, *len);
+
+        records[*len].take().map(|(_, count)| count)
+    }
+}
+
+impl<K : std_cmp::Eq + std_hash::Hash, const N : usize> SmallFrequencyMap<K, N> {
+    fn get_<Q>(
+        &self,
+        key : &Q,
+    ) -> &isize
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_hash::Hash + std_cmp::Eq + ?Sized,
+    {
+        match &self.storage {
+            Storage::Inline { records, len } => {
+                match Self::position_(&records[.. *len], key) {
+                    Some(ix) => records[ix].as_ref().map_or(&0, |(_, count)| count),
+                    None => &0,
+                }
+            },
+            Storage::Spilled(fm) => &fm[key],
+        }
+    }
+
+    fn position_<Q>(
+        records : &[Option<(K, isize)>],
+        key : &Q,
+    ) -> Option<usize>
+    where
+        K : std_borrow::Borrow<Q>,
+        Q : std_cmp::Eq + ?Sized,
+    {
+        records
+            .iter()
+            .position(|record| record.as_ref().is_some_and(|(k, _)| k.borrow() == key))
+    }
+}
+
+// Trait implementations
+
+impl<K, const N : usize> Default for SmallFrequencyMap<K, N> {
+    /// Creates an empty instance.
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K : std_cmp::Eq + std_hash::Hash, const N : usize> From<SmallFrequencyMap<K, N>> for FrequencyMap<K> {
+    /// Creates a [`FrequencyMap`] from the records of the given instance.
+    fn from(value : SmallFrequencyMap<K, N>) -> Self {
+        match value.storage {
+            Storage::Inline { records, .. } => {
+                let mut fm = FrequencyMap::with_capacity(N);
+
+                for (key, count) in records.into_iter().flatten() {
+                    fm.insert(key, count);
+                }
+
+                fm
+            },
+            Storage::Spilled(fm) => fm,
+        }
+    }
+}
+
+impl<K : std_cmp::Eq + std_hash::Hash, const N : usize> FromIterator<K> for SmallFrequencyMap<K, N> {
+    /// Creates an instance from an iterator of keys.
+    fn from_iter<T : IntoIterator<Item = K>>(iter : T) -> Self {
+        let mut sfm = Self::new();
+
+        for key in iter {
+            sfm.push(key);
+        }
+
+        sfm
+    }
+}
+
+impl<K, Q, const N : usize> std_ops::Index<&Q> for SmallFrequencyMap<K, N>
+where
+    K : std_cmp::Eq + std_hash::Hash + std_borrow::Borrow<Q>,
+    Q : std_cmp::Eq + std_hash::Hash + ?Sized,
+{
+    type Output = isize;
+
+    #[inline]
+    fn index(
+        &self,
+        key : &Q,
+    ) -> &Self::Output {
+        self.get_(key)
+    }
+}
+
+impl<K, const N : usize> IsEmpty for SmallFrequencyMap<K, N> {
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.is_empty_()
+    }
+}
+
+impl<K, const N : usize> Len for SmallFrequencyMap<K, N> {
+    /// Obtains the number of records.
+    #[inline]
+    fn len(&self) -> usize {
+        self.len_()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::SmallFrequencyMap;
+
+    use crate::containers::FrequencyMap;
+
+    use base_traits::IsEmpty;
+
+
+    fn sorted<K : Clone + Ord>(sfm : &SmallFrequencyMap<K, 4>) -> Vec<(K, isize)> {
+        let mut v : Vec<_> = sfm.iter().map(|(k, &c)| (k.clone(), c)).collect();
+
+        v.sort();
+
+        v
+    }
+
+    #[test]
+    fn TEST_SmallFrequencyMap_Default() {
+        let sfm = SmallFrequencyMap::<&str, 4>::default();
+
+        assert!(sfm.is_empty());
+        assert!(!sfm.is_spilled());
+        assert_eq!(0, sfm.len());
+        assert_eq!(0, sfm.total());
+        assert_eq!(0, sfm.get("GET"));
+        assert_eq!(0, sfm["GET"]);
+        assert_eq!(0, sfm.iter().count());
+    }
+
+    #[test]
+    fn TEST_SmallFrequencyMap_INLINE() {
+        let mut sfm : SmallFrequencyMap<&str, 4> = ["GET", "POST", "GET", "PUT", "GET"].into_iter().collect();
+
+        assert!(!sfm.is_spilled());
+        assert_eq!(3, sfm.len());
+        assert_eq!(5, sfm.total());
+        assert_eq!(3, sfm["GET"]);
+        assert_eq!(vec![("GET", 3), ("POST", 1), ("PUT", 1)], sorted(&sfm));
+
+        sfm.push_n("POST", -1);
+
+        assert_eq!(2, sfm.len());
+        assert!(!sfm.contains_key("POST"));
+
+        assert_eq!(Some(3), sfm.insert("GET", 7));
+        assert_eq!(Some(1), sfm.remove("PUT"));
+        assert_eq!(None, sfm.remove("PUT"));
+
+        assert_eq!(vec![("GET", 7)], sorted(&sfm));
+        assert_eq!(7, sfm.total());
+    }
+
+    #[test]
+    fn TEST_SmallFrequencyMap_SPILL() {
+        let mut sfm = SmallFrequencyMap::<i32, 4>::new();
+
+        for i in 0..4 {
+            sfm.push_n(i, i as isize + 1);
+        }
+
+        assert!(!sfm.is_spilled());
+
+        sfm.push(2);
+        sfm.push(4);
+
+        assert!(sfm.is_spilled());
+        assert_eq!(5, sfm.len());
+        assert_eq!(12, sfm.total());
+        assert_eq!(4, sfm[&2]);
+        assert_eq!(vec![(0, 1), (1, 2), (2, 4), (3, 4), (4, 1)], sorted(&sfm));
+
+        let fm = FrequencyMap::from(sfm.clone());
+
+        assert_eq!(5, fm.len());
+        assert_eq!(12, fm.total());
+
+        sfm.clear();
+
+        assert!(!sfm.is_spilled());
+        assert_eq!(0, sfm.len());
+    }
+
+    #[test]
+    fn TEST_SmallFrequencyMap_is_empty_WITH_ZERO_TOTAL() {
+        let mut sfm = SmallFrequencyMap::<&str, 4>::new();
+
+        sfm.push("a");
+        sfm.push_n("b", -1);
+
+        assert_eq!(0, sfm.total());
+        assert_eq!(2, sfm.len());
+        assert!(!sfm.is_empty());
+        assert!(!IsEmpty::is_empty(&sfm));
+
+        for key in ["c", "d", "e"] {
+            sfm.push_n(key, -1);
+        }
+        sfm.push_n("f", 3);
+
+        assert!(sfm.is_spilled());
+        assert_eq!(0, sfm.total());
+        assert!(!sfm.is_empty());
+
+        // as is the `FrequencyMap` into which it spills
+        let fm = FrequencyMap::from(sfm.clone());
+
+        assert_eq!(0, fm.total());
+        assert_eq!(sfm.len(), fm.len());
+        assert!(!fm.is_empty());
+
+        sfm.clear();
+
+        assert!(sfm.is_empty());
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
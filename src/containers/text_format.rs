@@ -0,0 +1,1008 @@
+// containers/text_format.rs : CSV, TSV, and JSON-lines import and export
+
+use super::{
+    FrequencyMap,
+    UnicodePointMap,
+};
+
+use std::{
+    cmp as std_cmp,
+    error as std_error,
+    fmt as std_fmt,
+    hash as std_hash,
+    io as std_io,
+    str as std_str,
+};
+
+
+/// The form in which code points are written when exporting a
+/// [`UnicodePointMap`].
+///
+/// Either form is accepted on import.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub enum CodePointFormat {
+    /// The character itself, e.g. `é`.
+    #[default]
+    Character,
+    /// Unicode notation, e.g. `U+00E9`.
+    Notation,
+}
+
+/// Error type for CSV, TSV, and JSON-lines import.
+#[derive(Debug)]
+pub enum ImportError {
+    /// An error occurred reading the input.
+    Io(std_io::Error),
+    /// The input is malformed at the given (1-based) line.
+    Parse {
+        line :    usize,
+        message : String,
+    },
+}
+
+impl std_fmt::Display for ImportError {
+    fn fmt(
+        &self,
+        f : &mut std_fmt::Formatter<'_>,
+    ) -> std_fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+            Self::Parse {
+                line,
+                message,
+            } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl std_error::Error for ImportError {
+    fn source(&self) -> Option<&(dyn std_error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Parse {
+                ..
+            } => None,
+        }
+    }
+}
+
+impl From<std_io::Error> for ImportError {
+    fn from(value : std_io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+
+// FrequencyMap
+
+impl<K : std_fmt::Display> FrequencyMap<K> {
+    /// Writes all records as CSV `key,count` lines, in arbitrary order and
+    /// without a header. Keys are written via [`std::fmt::Display`] and
+    /// quoted as required by RFC 4180.
+    pub fn write_csv<W : std_io::Write>(
+        &self,
+        w : W,
+    ) -> std_io::Result<()> {
+        write_records_(w, Format::Csv, self.iter().map(|(key, &count)| (key.to_string(), count)))
+    }
+
+    /// Writes all records as JSON lines of the form
+    /// `{"key":"...","count":N}`, in arbitrary order. Keys are written via
+    /// [`std::fmt::Display`].
+    pub fn write_json_lines<W : std_io::Write>(
+        &self,
+        w : W,
+    ) -> std_io::Result<()> {
+        write_records_(w, Format::JsonLines, self.iter().map(|(key, &count)| (key.to_string(), count)))
+    }
+
+    /// Writes all records as TSV `key<TAB>count` lines, in arbitrary order
+    /// and without a header. Keys are written via [`std::fmt::Display`],
+    /// with tab, newline, carriage return, and backslash escaped as `\t`,
+    /// `\n`, `\r`, and `\\`.
+    pub fn write_tsv<W : std_io::Write>(
+        &self,
+        w : W,
+    ) -> std_io::Result<()> {
+        write_records_(w, Format::Tsv, self.iter().map(|(key, &count)| (key.to_string(), count)))
+    }
+}
+
+impl<K : std_cmp::Eq + std_hash::Hash + std_str::FromStr> FrequencyMap<K> {
+    /// Reads CSV `key,count` lines, as written by
+    /// [`#write_csv()`](Self::write_csv), accumulating each into `self`
+    /// via [`#push_n()`](Self::push_n). Keys are parsed via
+    /// [`std::str::FromStr`]. Quoted keys may span lines, and retain their
+    /// line terminators. Blank lines are ignored.
+    ///
+    /// # Errors
+    /// If any line is malformed then nothing is accumulated.
+    pub fn read_csv<R : std_io::BufRead>(
+        &mut self,
+        r : R,
+    ) -> Result<(), ImportError> {
+        self.read_(r, Format::Csv)
+    }
+
+    /// Reads JSON lines, as written by
+    /// [`#write_json_lines()`](Self::write_json_lines), accumulating each
+    /// into `self` via [`#push_n()`](Self::push_n). Keys are parsed via
+    /// [`std::str::FromStr`]. Blank lines are ignored.
+    ///
+    /// # Errors
+    /// If any line is malformed then nothing is accumulated.
+    pub fn read_json_lines<R : std_io::BufRead>(
+        &mut self,
+        r : R,
+    ) -> Result<(), ImportError> {
+        self.read_(r, Format::JsonLines)
+    }
+
+    /// Reads TSV `key<TAB>count` lines, as written by
+    /// [`#write_tsv()`](Self::write_tsv), accumulating each into `self` via
+    /// [`#push_n()`](Self::push_n). Keys are parsed via
+    /// [`std::str::FromStr`]. Blank lines are ignored.
+    ///
+    /// # Errors
+    /// If any line is malformed then nothing is accumulated.
+    pub fn read_tsv<R : std_io::BufRead>(
+        &mut self,
+        r : R,
+    ) -> Result<(), ImportError> {
+        self.read_(r, Format::Tsv)
+    }
+
+    fn read_<R : std_io::BufRead>(
+        &mut self,
+        r : R,
+        format : Format,
+    ) -> Result<(), ImportError> {
+        let records = read_records_(r, format, |key| key.parse::<K>().ok())?;
+
+        for (key, count) in records {
+            self.push_n(key, count);
+        }
+
+        Ok(())
+    }
+}
+
+
+// UnicodePointMap
+
+impl UnicodePointMap {
+    /// Reads CSV `key,count` lines, as written by
+    /// [`#write_csv()`](Self::write_csv), accumulating each into `self`
+    /// via [`#push_n()`](Self::push_n). Keys may be in either
+    /// [`CodePointFormat`], and may be quoted, as is required for `'\r'`
+    /// and `'\n'`. Blank lines are ignored.
+    ///
+    /// # Errors
+    /// If any line is malformed then nothing is accumulated.
+    pub fn read_csv<R : std_io::BufRead>(
+        &mut self,
+        r : R,
+    ) -> Result<(), ImportError> {
+        self.read_(r, Format::Csv)
+    }
+
+    /// Reads JSON lines, as written by
+    /// [`#write_json_lines()`](Self::write_json_lines), accumulating each
+    /// into `self` via [`#push_n()`](Self::push_n). Keys may be in either
+    /// [`CodePointFormat`]. Blank lines are ignored.
+    ///
+    /// # Errors
+    /// If any line is malformed then nothing is accumulated.
+    pub fn read_json_lines<R : std_io::BufRead>(
+        &mut self,
+        r : R,
+    ) -> Result<(), ImportError> {
+        self.read_(r, Format::JsonLines)
+    }
+
+    /// Reads TSV `key<TAB>count` lines, as written by
+    /// [`#write_tsv()`](Self::write_tsv), accumulating each into `self` via
+    /// [`#push_n()`](Self::push_n). Keys may be in either
+    /// [`CodePointFormat`]. Blank lines are ignored.
+    ///
+    /// # Errors
+    /// If any line is malformed then nothing is accumulated.
+    pub fn read_tsv<R : std_io::BufRead>(
+        &mut self,
+        r : R,
+    ) -> Result<(), ImportError> {
+        self.read_(r, Format::Tsv)
+    }
+
+    /// Writes all records as CSV `key,count` lines, in arbitrary order and
+    /// without a header, with keys in the given `code_point_format`.
+    pub fn write_csv<W : std_io::Write>(
+        &self,
+        w : W,
+        code_point_format : CodePointFormat,
+    ) -> std_io::Result<()> {
+        write_records_(w, Format::Csv, self.records_(code_point_format))
+    }
+
+    /// Writes all records as JSON lines of the form
+    /// `{"key":"...","count":N}`, in arbitrary order, with keys in the
+    /// given `code_point_format`.
+    pub fn write_json_lines<W : std_io::Write>(
+        &self,
+        w : W,
+        code_point_format : CodePointFormat,
+    ) -> std_io::Result<()> {
+        write_records_(w, Format::JsonLines, self.records_(code_point_format))
+    }
+
+    /// Writes all records as TSV `key<TAB>count` lines, in arbitrary order
+    /// and without a header, with keys in the given `code_point_format`.
+    pub fn write_tsv<W : std_io::Write>(
+        &self,
+        w : W,
+        code_point_format : CodePointFormat,
+    ) -> std_io::Result<()> {
+        write_records_(w, Format::Tsv, self.records_(code_point_format))
+    }
+
+    fn read_<R : std_io::BufRead>(
+        &mut self,
+        r : R,
+        format : Format,
+    ) -> Result<(), ImportError> {
+        let records = read_records_(r, format, parse_code_point_)?;
+
+        for (c, count) in records {
+            self.push_n(c, count);
+        }
+
+        Ok(())
+    }
+
+    fn records_(
+        &self,
+        code_point_format : CodePointFormat,
+    ) -> impl Iterator<Item = (String, isize)> + '_ {
+        self.iter().map(move |(c, count)| {
+            let key = match code_point_format {
+                CodePointFormat::Character => c.to_string(),
+                CodePointFormat::Notation => format!("U+{:04X}", c as u32),
+            };
+
+            (key, count)
+        })
+    }
+}
+
+
+// Implementation
+
+#[derive(Clone)]
+#[derive(Copy)]
+enum Format {
+    Csv,
+    Tsv,
+    JsonLines,
+}
+
+fn parse_code_point_(key : &str) -> Option<char> {
+    let mut chars = key.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => {
+            let hex = key.strip_prefix("U+")?;
+
+            if hex.is_empty() || hex.len() > 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                None
+            } else {
+                u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+            }
+        },
+    }
+}
+
+fn write_records_<W, I>(
+    mut w : W,
+    format : Format,
+    records : I,
+) -> std_io::Result<()>
+where
+    W : std_io::Write,
+    I : Iterator<Item = (String, isize)>,
+{
+    let mut line = String::new();
+
+    for (key, count) in records {
+        line.clear();
+
+        match format {
+            Format::Csv => {
+                if key.contains([',', '"', '\r', '\n']) || key.starts_with(' ') || key.ends_with(' ') {
+                    line.push('"');
+                    line.push_str(&key.replace('"', "\"\""));
+                    line.push('"');
+                } else {
+                    line.push_str(&key);
+                }
+                line.push(',');
+                line.push_str(&count.to_string());
+            },
+            Format::Tsv => {
+                for c in key.chars() {
+                    match c {
+                        '\t' => line.push_str("\\t"),
+                        '\n' => line.push_str("\\n"),
+                        '\r' => line.push_str("\\r"),
+                        '\\' => line.push_str("\\\\"),
+                        _ => line.push(c),
+                    }
+                }
+                line.push('\t');
+                line.push_str(&count.to_string());
+            },
+            Format::JsonLines => {
+                line.push_str("{\"key\":");
+                json::push_string(&mut line, &key);
+                line.push_str(",\"count\":");
+                line.push_str(&count.to_string());
+                line.push('}');
+            },
+        }
+
+        line.push('\n');
+
+        w.write_all(line.as_bytes())?;
+    }
+
+    w.flush()
+}
+
+fn read_records_<R, K, F>(
+    mut r : R,
+    format : Format,
+    parse_key : F,
+) -> Result<Vec<(K, isize)>, ImportError>
+where
+    R : std_io::BufRead,
+    F : Fn(&str) -> Option<K>,
+{
+    let mut records = Vec::new();
+    let mut line = String::new();
+    let mut physical_line_number = 0;
+
+    loop {
+        line.clear();
+
+        if 0 == r.read_line(&mut line)? {
+            break;
+        }
+
+        physical_line_number += 1;
+
+        let line_number = physical_line_number;
+
+        // a quoted CSV field may span lines, in which case the line
+        // terminators within it, including any `'\r'`, are part of the key
+        if let Format::Csv = format {
+            while csv::has_open_quote(&line) {
+                if 0 == r.read_line(&mut line)? {
+                    return Err(ImportError::Parse {
+                        line :    line_number,
+                        message : "unterminated quoted field".into(),
+                    });
+                }
+
+                physical_line_number += 1;
+            }
+        }
+
+        strip_line_terminator_(&mut line);
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let parsed = match format {
+            Format::Csv => csv::parse_record(&line),
+            Format::Tsv => tsv::parse_record(&line),
+            Format::JsonLines => json::parse_record(&line),
+        };
+
+        let (key, count) = parsed.map_err(|message| {
+            ImportError::Parse {
+                line : line_number,
+                message,
+            }
+        })?;
+
+        let count = count.trim().parse::<isize>().map_err(|_| {
+            ImportError::Parse {
+                line :    line_number,
+                message : format!("invalid count {count:?}"),
+            }
+        })?;
+
+        let key = parse_key(&key).ok_or_else(|| {
+            ImportError::Parse {
+                line :    line_number,
+                message : format!("invalid key {key:?}"),
+            }
+        })?;
+
+        records.push((key, count));
+    }
+
+    Ok(records)
+}
+
+/// Removes the trailing `"\n"` or `"\r\n"`, if any, from `line`.
+fn strip_line_terminator_(line : &mut String) {
+    if line.ends_with('\n') {
+        line.pop();
+
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+}
+
+mod csv {
+
+    /// Indicates whether `line` ends within a quoted field.
+    pub(super) fn has_open_quote(line : &str) -> bool {
+        let mut in_quotes = false;
+        let mut at_field_start = true;
+
+        for c in line.chars() {
+            match c {
+                '"' if in_quotes || at_field_start => in_quotes = !in_quotes,
+                ',' if !in_quotes => {
+                    at_field_start = true;
+
+                    continue;
+                },
+                _ => {},
+            }
+
+            at_field_start = false;
+        }
+
+        in_quotes
+    }
+
+    /// Parses a `key,count` record, obtaining the unquoted key and the
+    /// (unparsed) count.
+    pub(super) fn parse_record(line : &str) -> Result<(String, String), String> {
+        let (key, rest) = if let Some(quoted) = line.strip_prefix('"') {
+            let mut key = String::new();
+            let mut chars = quoted.char_indices();
+
+            loop {
+                match chars.next() {
+                    Some((ix, '"')) => {
+                        if quoted[ix + 1..].starts_with('"') {
+                            key.push('"');
+                            chars.next();
+                        } else {
+                            break (key, &quoted[ix + 1..]);
+                        }
+                    },
+                    Some((_, c)) => key.push(c),
+                    None => return Err("unterminated quoted field".into()),
+                }
+            }
+        } else {
+            match line.find(',') {
+                Some(ix) => (line[..ix].to_string(), &line[ix..]),
+                None => (line.to_string(), ""),
+            }
+        };
+
+        match rest.strip_prefix(',') {
+            Some(count) if !count.contains(',') => Ok((key, count.to_string())),
+            Some(_) => Err("too many fields".into()),
+            None => Err("expected 2 fields".into()),
+        }
+    }
+}
+
+mod tsv {
+
+    /// Parses a `key<TAB>count` record, obtaining the unescaped key and
+    /// the (unparsed) count.
+    pub(super) fn parse_record(line : &str) -> Result<(String, String), String> {
+        let mut fields = line.split('\t');
+
+        let (escaped_key, count) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(key), Some(count), None) => (key, count),
+            (_, _, Some(_)) => return Err("too many fields".into()),
+            _ => return Err("expected 2 fields".into()),
+        };
+
+        let mut key = String::with_capacity(escaped_key.len());
+        let mut chars = escaped_key.chars();
+
+        while let Some(c) = chars.next() {
+            if '\\' == c {
+                match chars.next() {
+                    Some('t') => key.push('\t'),
+                    Some('n') => key.push('\n'),
+                    Some('r') => key.push('\r'),
+                    Some('\\') => key.push('\\'),
+                    _ => return Err("invalid escape sequence".into()),
+                }
+            } else {
+                key.push(c);
+            }
+        }
+
+        Ok((key, count.to_string()))
+    }
+}
+
+mod json {
+    use std::{
+        fmt::Write as _,
+        iter as std_iter,
+        str as std_str,
+    };
+
+
+    /// Appends `s` to `line` as a JSON string literal.
+    pub(super) fn push_string(
+        line : &mut String,
+        s : &str,
+    ) {
+        line.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => line.push_str("\\\""),
+                '\\' => line.push_str("\\\\"),
+                '\n' => line.push_str("\\n"),
+                '\r' => line.push_str("\\r"),
+                '\t' => line.push_str("\\t"),
+                _ if c.is_control() => {
+                    let _ = write!(line, "\\u{:04x}", c as u32);
+                },
+                _ => line.push(c),
+            }
+        }
+        line.push('"');
+    }
+
+    /// Parses a `{"key":"...","count":N}` record, whose members may be in
+    /// either order, obtaining the key and the (unparsed) count.
+    pub(super) fn parse_record(line : &str) -> Result<(String, String), String> {
+        let mut chars = line.trim().chars().peekable();
+        let mut key = None;
+        let mut count = None;
+
+        expect_(&mut chars, '{')?;
+
+        loop {
+            skip_whitespace_(&mut chars);
+
+            let name = parse_string_(&mut chars)?;
+
+            skip_whitespace_(&mut chars);
+            expect_(&mut chars, ':')?;
+            skip_whitespace_(&mut chars);
+
+            match name.as_str() {
+                "key" if key.is_none() => {
+                    key = Some(parse_string_(&mut chars)?);
+                },
+                "count" if count.is_none() => {
+                    let mut n = String::new();
+
+                    while let Some(&c) = chars.peek() {
+                        if '-' == c || c.is_ascii_digit() {
+                            n.push(c);
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+
+                    count = Some(n);
+                },
+                _ => return Err(format!("unexpected member {name:?}")),
+            }
+
+            skip_whitespace_(&mut chars);
+
+            match chars.next() {
+                Some(',') => {},
+                Some('}') => break,
+                _ => return Err("expected ',' or '}'".into()),
+            }
+        }
+
+        if chars.next().is_some() {
+            return Err("unexpected trailing characters".into());
+        }
+
+        match (key, count) {
+            (Some(key), Some(count)) => Ok((key, count)),
+            (None, _) => Err("missing member \"key\"".into()),
+            (_, None) => Err("missing member \"count\"".into()),
+        }
+    }
+
+    type Chars<'a> = std_iter::Peekable<std_str::Chars<'a>>;
+
+    fn expect_(
+        chars : &mut Chars<'_>,
+        expected : char,
+    ) -> Result<(), String> {
+        match chars.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(format!("expected {expected:?}")),
+        }
+    }
+
+    fn parse_hex4_(chars : &mut Chars<'_>) -> Result<u32, String> {
+        let hex : String = chars.by_ref().take(4).collect();
+
+        if 4 == hex.len() && hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            Ok(u32::from_str_radix(&hex, 16).unwrap_or_default())
+        } else {
+            Err("invalid \\u escape".into())
+        }
+    }
+
+    fn parse_string_(chars : &mut Chars<'_>) -> Result<String, String> {
+        expect_(chars, '"')?;
+
+        let mut s = String::new();
+
+        loop {
+            match chars.next() {
+                Some('"') => return Ok(s),
+                Some('\\') => {
+                    match chars.next() {
+                        Some('"') => s.push('"'),
+                        Some('\\') => s.push('\\'),
+                        Some('/') => s.push('/'),
+                        Some('b') => s.push('\u{8}'),
+                        Some('f') => s.push('\u{c}'),
+                        Some('n') => s.push('\n'),
+                        Some('r') => s.push('\r'),
+                        Some('t') => s.push('\t'),
+                        Some('u') => {
+                            let hi = parse_hex4_(chars)?;
+
+                            let cp = if (0xd800..0xdc00).contains(&hi) {
+                                if chars.next() != Some('\\') || chars.next() != Some('u') {
+                                    return Err("unpaired surrogate".into());
+                                }
+
+                                let lo = parse_hex4_(chars)?;
+
+                                if !(0xdc00..0xe000).contains(&lo) {
+                                    return Err("unpaired surrogate".into());
+                                }
+
+                                0x10000 + ((hi - 0xd800) << 10) + (lo - 0xdc00)
+                            } else {
+                                hi
+                            };
+
+                            s.push(char::from_u32(cp).ok_or("unpaired surrogate")?);
+                        },
+                        _ => return Err("invalid escape sequence".into()),
+                    }
+                },
+                Some(c) => s.push(c),
+                None => return Err("unterminated string".into()),
+            }
+        }
+    }
+
+    fn skip_whitespace_(chars : &mut Chars<'_>) {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::{
+        CodePointFormat,
+        ImportError,
+    };
+
+    use crate::containers::{
+        FrequencyMap,
+        UnicodePointMap,
+    };
+
+
+    fn sorted_lines(v : &[u8]) -> Vec<&str> {
+        let mut lines : Vec<_> = std::str::from_utf8(v).unwrap().lines().collect();
+
+        lines.sort();
+
+        lines
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_write_csv_1() {
+        let fm = FrequencyMap::<String>::from([
+            // insert list
+            ("cat".to_string(), 3),
+            ("a,b".to_string(), 1),
+            ("say \"hi\"".to_string(), -2),
+        ]);
+
+        let mut v = Vec::new();
+
+        fm.write_csv(&mut v).unwrap();
+
+        assert_eq!(vec!["\"a,b\",1", "\"say \"\"hi\"\"\",-2", "cat,3"], sorted_lines(&v));
+
+        let mut fm2 = FrequencyMap::<String>::new();
+
+        fm2.read_csv(v.as_slice()).unwrap();
+
+        assert_eq!(3, fm2.len());
+        assert_eq!(2, fm2.total());
+        assert_eq!(3, fm2["cat"]);
+        assert_eq!(1, fm2["a,b"]);
+        assert_eq!(-2, fm2["say \"hi\""]);
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_write_csv_CARRIAGE_RETURNS() {
+        let fm = FrequencyMap::<String>::from([
+            // insert list
+            ("x\r\ny".to_string(), 2),
+            ("z\r".to_string(), 1),
+            ("\r\n".to_string(), -1),
+        ]);
+
+        let mut v = Vec::new();
+
+        fm.write_csv(&mut v).unwrap();
+
+        let mut fm2 = FrequencyMap::<String>::new();
+
+        fm2.read_csv(v.as_slice()).unwrap();
+
+        assert_eq!(3, fm2.len());
+        assert_eq!(2, fm2.total());
+        assert_eq!(2, fm2["x\r\ny"]);
+        assert_eq!(1, fm2["z\r"]);
+        assert_eq!(-1, fm2["\r\n"]);
+
+        // line terminators outside quoted fields may be either form
+        let mut fm3 = FrequencyMap::<String>::new();
+
+        fm3.read_csv("a,1\r\n\"b\r\nc\",2\r\n\r\nd,3\n".as_bytes()).unwrap();
+
+        assert_eq!(3, fm3.len());
+        assert_eq!(1, fm3["a"]);
+        assert_eq!(2, fm3["b\r\nc"]);
+        assert_eq!(3, fm3["d"]);
+
+        match fm3.read_csv("a,1\r\n\"b\r\nc\",2\r\nx,y\r\n".as_bytes()) {
+            Err(e) => assert_eq!("line 4: invalid count \"y\"", e.to_string()),
+            r => panic!("unexpected result: {r:?}"),
+        }
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_read_csv_ACCUMULATES() {
+        let input = "1,2\n\n2,1\n1,3\n\"3\",-1\n\"\n\",1\n";
+
+        let mut fm = FrequencyMap::<i32>::from([(1, 10)]);
+
+        fm.read_csv(input.as_bytes()).unwrap_err();
+
+        // the multi-line key fails to parse as `i32`, so nothing is accumulated
+        assert_eq!(10, fm.total());
+
+        fm.read_csv(&input.as_bytes()[..input.len() - 6]).unwrap();
+
+        assert_eq!(3, fm.len());
+        assert_eq!(15, fm.total());
+        assert_eq!(15, fm[&1]);
+        assert_eq!(1, fm[&2]);
+        assert_eq!(-1, fm[&3]);
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_read_csv_ERRORS() {
+        let mut fm = FrequencyMap::<i32>::new();
+
+        match fm.read_csv("1,2\n2,x\n".as_bytes()) {
+            Err(ImportError::Parse {
+                line,
+                message,
+            }) => {
+                assert_eq!(2, line);
+                assert_eq!("invalid count \"x\"", message);
+            },
+            r => panic!("unexpected result: {r:?}"),
+        }
+
+        match fm.read_csv("1,2\n\nx,1\n".as_bytes()) {
+            Err(e) => assert_eq!("line 3: invalid key \"x\"", e.to_string()),
+            r => panic!("unexpected result: {r:?}"),
+        }
+
+        match fm.read_csv("1,2,3\n".as_bytes()) {
+            Err(e) => assert_eq!("line 1: too many fields", e.to_string()),
+            r => panic!("unexpected result: {r:?}"),
+        }
+
+        match fm.read_csv("1\n".as_bytes()) {
+            Err(e) => assert_eq!("line 1: expected 2 fields", e.to_string()),
+            r => panic!("unexpected result: {r:?}"),
+        }
+
+        match fm.read_csv("\"1,2\n".as_bytes()) {
+            Err(e) => assert_eq!("line 1: unterminated quoted field", e.to_string()),
+            r => panic!("unexpected result: {r:?}"),
+        }
+
+        assert!(fm.is_empty());
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_write_tsv_1() {
+        let fm = FrequencyMap::<String>::from([
+            // insert list
+            ("cat".to_string(), 3),
+            ("a\tb\\c\nd".to_string(), 1),
+        ]);
+
+        let mut v = Vec::new();
+
+        fm.write_tsv(&mut v).unwrap();
+
+        assert_eq!(vec!["a\\tb\\\\c\\nd\t1", "cat\t3"], sorted_lines(&v));
+
+        let mut fm2 = FrequencyMap::<String>::new();
+
+        fm2.read_tsv(v.as_slice()).unwrap();
+        fm2.read_tsv("cat\t-1\n".as_bytes()).unwrap();
+
+        assert_eq!(2, fm2.len());
+        assert_eq!(3, fm2.total());
+        assert_eq!(2, fm2["cat"]);
+        assert_eq!(1, fm2["a\tb\\c\nd"]);
+
+        match fm2.read_tsv("cat\t1\ndog\n".as_bytes()) {
+            Err(e) => assert_eq!("line 2: expected 2 fields", e.to_string()),
+            r => panic!("unexpected result: {r:?}"),
+        }
+    }
+
+    #[test]
+    fn TEST_FrequencyMap_write_json_lines_1() {
+        let fm = FrequencyMap::<String>::from([
+            // insert list
+            ("cat".to_string(), 3),
+            ("\"q\"\u{1}".to_string(), -1),
+        ]);
+
+        let mut v = Vec::new();
+
+        fm.write_json_lines(&mut v).unwrap();
+
+        assert_eq!(
+            vec!["{\"key\":\"\\\"q\\\"\\u0001\",\"count\":-1}", "{\"key\":\"cat\",\"count\":3}"],
+            sorted_lines(&v)
+        );
+
+        let mut fm2 = FrequencyMap::<String>::new();
+
+        fm2.read_json_lines(v.as_slice()).unwrap();
+        fm2.read_json_lines(" { \"count\" : 2 , \"key\" : \"\\ud83d\\udc3b\" } \n".as_bytes()).unwrap();
+
+        assert_eq!(3, fm2.len());
+        assert_eq!(4, fm2.total());
+        assert_eq!(3, fm2["cat"]);
+        assert_eq!(-1, fm2["\"q\"\u{1}"]);
+        assert_eq!(2, fm2["🐻"]);
+
+        match fm2.read_json_lines("{\"key\":\"cat\"}\n".as_bytes()) {
+            Err(e) => assert_eq!("line 1: missing member \"count\"", e.to_string()),
+            r => panic!("unexpected result: {r:?}"),
+        }
+
+        match fm2.read_json_lines("{\"key\":\"cat\",\"count\":1,\"x\":1}\n".as_bytes()) {
+            Err(e) => assert_eq!("line 1: unexpected member \"x\"", e.to_string()),
+            r => panic!("unexpected result: {r:?}"),
+        }
+    }
+
+    #[test]
+    fn TEST_UnicodePointMap_write_csv_1() {
+        let upm = UnicodePointMap::from([
+            // insert list
+            ('a', 2),
+            (',', 1),
+            ('é', 1),
+            ('🐻', 3),
+        ]);
+
+        let mut v = Vec::new();
+
+        upm.write_csv(&mut v, CodePointFormat::Character).unwrap();
+
+        assert_eq!(vec!["\",\",1", "a,2", "é,1", "🐻,3"], sorted_lines(&v));
+
+        v.clear();
+
+        upm.write_csv(&mut v, CodePointFormat::Notation).unwrap();
+
+        assert_eq!(vec!["U+002C,1", "U+0061,2", "U+00E9,1", "U+1F43B,3"], sorted_lines(&v));
+
+        let mut upm2 = UnicodePointMap::default();
+
+        upm2.read_csv(v.as_slice()).unwrap();
+        upm2.read_csv("a,1\n".as_bytes()).unwrap();
+
+        assert_eq!(4, upm2.len());
+        assert_eq!(8, upm2.total());
+        assert_eq!(3, upm2['a']);
+        assert_eq!(1, upm2[',']);
+        assert_eq!(1, upm2['é']);
+        assert_eq!(3, upm2['🐻']);
+
+        match upm2.read_csv("ab,1\n".as_bytes()) {
+            Err(e) => assert_eq!("line 1: invalid key \"ab\"", e.to_string()),
+            r => panic!("unexpected result: {r:?}"),
+        }
+
+        match upm2.read_csv("U+D800,1\n".as_bytes()) {
+            Err(e) => assert_eq!("line 1: invalid key \"U+D800\"", e.to_string()),
+            r => panic!("unexpected result: {r:?}"),
+        }
+    }
+
+    #[test]
+    fn TEST_UnicodePointMap_write_tsv_AND_json_lines() {
+        let upm = UnicodePointMap::from([
+            // insert list
+            ('\t', 2),
+            ('"', 1),
+        ]);
+
+        let mut tsv = Vec::new();
+        let mut jsonl = Vec::new();
+
+        upm.write_tsv(&mut tsv, CodePointFormat::Character).unwrap();
+        upm.write_json_lines(&mut jsonl, CodePointFormat::Notation).unwrap();
+
+        assert_eq!(vec!["\"\t1", "\\t\t2"], sorted_lines(&tsv));
+        assert_eq!(
+            vec!["{\"key\":\"U+0009\",\"count\":2}", "{\"key\":\"U+0022\",\"count\":1}"],
+            sorted_lines(&jsonl)
+        );
+
+        let mut upm2 = UnicodePointMap::default();
+
+        upm2.read_tsv(tsv.as_slice()).unwrap();
+        upm2.read_json_lines(jsonl.as_slice()).unwrap();
+
+        assert_eq!(2, upm2.len());
+        assert_eq!(6, upm2.total());
+        assert_eq!(4, upm2['\t']);
+        assert_eq!(2, upm2['"']);
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
@@ -1,14 +1,19 @@
 // containers/unicode_point_map.rs : `UnicodePointMap`
 
+use crate::hash::{
+    hash_map,
+    HashMap,
+};
+
 use base_traits::{
     IsEmpty,
     Len,
 };
 
-use std::{
-    collections::HashMap,
-    ops as std_ops,
-};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use core::ops as std_ops;
 
 
 /// Constants
@@ -28,7 +33,7 @@ mod util {
 
     use super::constants::MAXIMUM_VALID_CHAR_VALUE;
 
-    use std::mem as std_mem;
+    use core::mem as std_mem;
 
 
     /// Converts the given `char` value to a `usize` value that is expected,
@@ -81,6 +86,7 @@ mod util {
 
 /// A container that measures the frequencies of the unique code points that
 /// it contains.
+#[derive(Clone)]
 #[derive(Debug)]
 pub struct UnicodePointMap {
     /// Contiguous storage for common characters.
@@ -357,6 +363,47 @@ impl UnicodePointMap {
             }
         }
     }
+
+    /// Retains only the records specified by the predicate.
+    pub fn retain<F>(
+        &mut self,
+        mut f : F,
+    )
+    where
+        F : FnMut(char, isize) -> bool,
+    {
+        for (ix, count) in self.vec.iter_mut().enumerate() {
+            if 0 != *count {
+                // NOTE: as for `UnicodePointMapIter`, it is an invariant of
+                // the design that `ix` is a valid code point
+                let c = unsafe { char::from_u32_unchecked(ix as u32) };
+
+                if !f(c, *count) {
+                    self.len -= 1;
+                    self.total -= *count as i64;
+
+                    *count = 0;
+                }
+            }
+        }
+
+        let mut dropped_len = 0;
+        let mut dropped_total = 0;
+
+        self.map.retain(|&c, count| {
+            if f(c, *count) {
+                true
+            } else {
+                dropped_len += 1;
+                dropped_total += *count as i64;
+
+                false
+            }
+        });
+
+        self.len -= dropped_len;
+        self.total -= dropped_total;
+    }
 }
 
 // Non-mutating methods
@@ -424,7 +471,7 @@ pub struct UnicodePointMapIter<'a> {
     /// (Optional) index into the vector.
     vec_index : Option<usize>,
     /// (Optional) interator into the map.
-    map_iter : Option<std::collections::hash_map::Iter<'a, char, isize>>,
+    map_iter : Option<hash_map::Iter<'a, char, isize>>,
 }
 
 impl Iterator for UnicodePointMapIter<'_> {
@@ -516,6 +563,29 @@ impl UnicodePointMap {
 // Implementation
 
 impl UnicodePointMap {
+    /// The extent of the contiguous storage.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn contiguous_ceiling_(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// The contiguous storage and the map for other characters.
+    #[inline]
+    pub(crate) fn storage_(&self) -> (&[isize], &HashMap<char, isize>) {
+        (&self.vec, &self.map)
+    }
+
+    /// Creates an empty instance with the same contiguous ceiling.
+    pub(crate) fn empty_like_(&self) -> Self {
+        Self {
+            vec :   vec![0; self.vec.len()],
+            map :   HashMap::new(),
+            len :   0,
+            total : 0,
+        }
+    }
+
     #[inline]
     fn is_empty_(&self) -> bool {
         0 == self.len
@@ -1321,6 +1391,50 @@ mod tests {
         assert_eq!(0, upm['.']);
         assert_eq!(0, upm[',']);
     }
+
+    #[test]
+    fn TEST_validate_DETECTS_CORRUPTION() {
+        use crate::containers::InvariantError;
+
+        let mut upm = UnicodePointMap::new('\u{80}');
+
+        upm.push_n('a', 2);
+        upm.push_n('€', 3);
+
+        assert_eq!(Ok(()), upm.validate());
+
+        {
+            let mut upm = upm.clone();
+
+            upm.total += 1;
+
+            assert_eq!(Err(InvariantError::TotalMismatch { stored : 6, computed : 5 }), upm.validate());
+        }
+
+        {
+            let mut upm = upm.clone();
+
+            upm.len = 3;
+
+            assert_eq!(Err(InvariantError::LenMismatch { stored : 3, computed : 2 }), upm.validate());
+        }
+
+        {
+            let mut upm = upm.clone();
+
+            upm.map.insert('\u{2000}', 0);
+
+            assert_eq!(Err(InvariantError::ZeroCountRecord { code_point : Some('\u{2000}') }), upm.validate());
+        }
+
+        {
+            let mut upm = upm.clone();
+
+            upm.map.insert('b', 1);
+
+            assert_eq!(Err(InvariantError::MisplacedCodePoint { code_point : 'b', ceiling : 0x80 }), upm.validate());
+        }
+    }
 }
 
 
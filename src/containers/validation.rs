@@ -0,0 +1,262 @@
+// containers/validation.rs : `InvariantError`
+
+use super::{
+    FrequencyMap,
+    UnicodePointMap,
+};
+
+use core::fmt as std_fmt;
+
+
+/// Error type for the invariant validation methods
+/// [`FrequencyMap::validate()`] and [`UnicodePointMap::validate()`].
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub enum InvariantError {
+    /// A record has a count of 0. `code_point` is the code point of the
+    /// record in the case of a [`UnicodePointMap`], and `None` otherwise.
+    ZeroCountRecord {
+        code_point : Option<char>,
+    },
+    /// A code point below the contiguous ceiling of a [`UnicodePointMap`]
+    /// is stored in its hash map, rather than in its contiguous storage.
+    MisplacedCodePoint {
+        code_point : char,
+        ceiling :    usize,
+    },
+    /// The stored number of records does not equal the number of records
+    /// with a non-zero count.
+    LenMismatch {
+        stored :   usize,
+        computed : usize,
+    },
+    /// The stored total does not equal the sum of the counts.
+    TotalMismatch {
+        stored :   i64,
+        computed : i64,
+    },
+}
+
+impl std_fmt::Display for InvariantError {
+    fn fmt(
+        &self,
+        f : &mut std_fmt::Formatter<'_>,
+    ) -> std_fmt::Result {
+        match self {
+            Self::ZeroCountRecord {
+                code_point: Some(c),
+            } => write!(f, "record for code point U+{:04X} has a count of 0", *c as u32),
+            Self::ZeroCountRecord {
+                code_point: None,
+            } => f.write_str("record has a count of 0"),
+            Self::MisplacedCodePoint {
+                code_point,
+                ceiling,
+            } => write!(f, "code point U+{:04X} is below the contiguous ceiling {ceiling} but is stored in the map", *code_point as u32),
+            Self::LenMismatch {
+                stored,
+                computed,
+            } => write!(f, "stored length {stored} does not equal number of non-zero records {computed}"),
+            Self::TotalMismatch {
+                stored,
+                computed,
+            } => write!(f, "stored total {stored} does not equal sum of counts {computed}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvariantError {}
+
+
+// FrequencyMap
+
+impl<K> FrequencyMap<K> {
+    /// Verifies the internal consistency of the instance, failing with the
+    /// first violated invariant found.
+    ///
+    /// This is intended for use in debug builds, e.g. after bulk
+    /// operations, and in fuzz and property-based tests.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvariantError::ZeroCountRecord`] if any record has a count of
+    ///   0;
+    /// - [`InvariantError::TotalMismatch`] if `#total()` does not equal the
+    ///   sum of the counts;
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let mut computed : i64 = 0;
+
+        for (_, &count) in self.iter() {
+            if 0 == count {
+                return Err(InvariantError::ZeroCountRecord {
+                    code_point : None,
+                });
+            }
+
+            computed = computed.wrapping_add(count as i64);
+        }
+
+        let stored = self.total() as i64;
+
+        if stored != computed {
+            return Err(InvariantError::TotalMismatch {
+                stored,
+                computed,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+
+// UnicodePointMap
+
+impl UnicodePointMap {
+    /// Verifies the internal consistency of the instance, failing with the
+    /// first violated invariant found.
+    ///
+    /// This is intended for use in debug builds, e.g. after bulk
+    /// operations, and in fuzz and property-based tests.
+    ///
+    /// # Errors
+    ///
+    /// - [`InvariantError::ZeroCountRecord`] if any record in the hash map
+    ///   has a count of 0;
+    /// - [`InvariantError::MisplacedCodePoint`] if any code point below the
+    ///   contiguous ceiling is stored in the hash map;
+    /// - [`InvariantError::LenMismatch`] if `#len()` does not equal the
+    ///   number of code points with a non-zero count;
+    /// - [`InvariantError::TotalMismatch`] if `#total()` does not equal the
+    ///   sum of the counts;
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let (vec, map) = self.storage_();
+
+        let mut len = 0;
+        let mut computed : i64 = 0;
+
+        for &count in vec {
+            if 0 != count {
+                len += 1;
+                computed = computed.wrapping_add(count as i64);
+            }
+        }
+
+        for (&c, &count) in map {
+            if 0 == count {
+                return Err(InvariantError::ZeroCountRecord {
+                    code_point : Some(c),
+                });
+            }
+
+            if (c as usize) < vec.len() {
+                return Err(InvariantError::MisplacedCodePoint {
+                    code_point : c,
+                    ceiling :    vec.len(),
+                });
+            }
+
+            len += 1;
+            computed = computed.wrapping_add(count as i64);
+        }
+
+        if self.len() != len {
+            return Err(InvariantError::LenMismatch {
+                stored :   self.len(),
+                computed : len,
+            });
+        }
+
+        if self.total() != computed {
+            return Err(InvariantError::TotalMismatch {
+                stored : self.total(),
+                computed,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::InvariantError;
+
+    use crate::containers::{
+        FrequencyMap,
+        UnicodePointMap,
+    };
+
+
+    #[test]
+    fn TEST_FrequencyMap_validate_1() {
+        let mut fm = FrequencyMap::<&str>::from([
+            // insert list
+            ("a", 3),
+            ("b", -1),
+        ]);
+
+        assert_eq!(Ok(()), fm.validate());
+
+        fm.push_n("a", -3);
+        fm.push_n("c", 10);
+        fm.retain(|_, count| count > 0);
+
+        assert_eq!(Ok(()), fm.validate());
+        assert_eq!(Ok(()), FrequencyMap::<i32>::new().validate());
+    }
+
+    #[test]
+    fn TEST_UnicodePointMap_validate_1() {
+        let mut upm = UnicodePointMap::new('\u{80}');
+
+        assert_eq!(Ok(()), upm.validate());
+
+        upm.push_n('a', 3);
+        upm.push_n('é', 2);
+        upm.push_n('€', -4);
+        upm.push('a');
+        upm.insert('é', 0);
+
+        assert_eq!(Ok(()), upm.validate());
+        assert_eq!(2, upm.len());
+        assert_eq!(0, upm.total());
+    }
+
+    #[test]
+    fn TEST_InvariantError_Display() {
+        assert_eq!(
+            "code point U+0041 is below the contiguous ceiling 128 but is stored in the map",
+            InvariantError::MisplacedCodePoint {
+                code_point : 'A',
+                ceiling :    128,
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "stored total 3 does not equal sum of counts 4",
+            InvariantError::TotalMismatch {
+                stored :   3,
+                computed : 4,
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "record for code point U+20AC has a count of 0",
+            InvariantError::ZeroCountRecord {
+                code_point : Some('€'),
+            }
+            .to_string()
+        );
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
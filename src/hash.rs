@@ -0,0 +1,47 @@
+// hash.rs : hash-based collections, from `std` or `hashbrown`
+
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{
+    hash_map,
+    HashMap,
+    HashSet,
+};
+
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::{
+    hash_map,
+    HashMap,
+    HashSet,
+};
+
+
+/// The number of control bytes beyond the buckets in the allocation of a
+/// hash table, being the width of the SIMD group used for probing (which
+/// is at most 16).
+const CONTROL_GROUP_WIDTH : usize = 16;
+
+
+/// Estimates the heap memory, in bytes, allocated by a hash table with
+/// the given `capacity` and entries of type `(K, V)`.
+///
+/// This reflects the layout of the SwissTable implementation that is used
+/// by both `std` and `hashbrown`: a power-of-two number of buckets, of
+/// which 7/8ths are usable (or all but one, for fewer than 8 buckets),
+/// each comprising one entry and one control byte.
+pub(crate) fn table_heap_size_bytes<K, V>(capacity : usize) -> usize {
+    if 0 == capacity {
+        return 0;
+    }
+
+    let buckets = if capacity < 8 {
+        capacity + 1
+    } else {
+        capacity / 7 * 8
+    };
+
+    buckets * core::mem::size_of::<(K, V)>() + buckets + CONTROL_GROUP_WIDTH
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
@@ -4,18 +4,27 @@
 // /////////////////////////////////////////////////////////
 // crate-level feature definitions
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
 
 // /////////////////////////////////////////////////////////
 // crate-level feature discrimination
 
+#[cfg(not(any(feature = "std", feature = "hashbrown")))]
+compile_error!("either the \"std\" feature or the \"hashbrown\" feature must be specified");
+
 
 // /////////////////////////////////////////////////////////
 // imports
 
+extern crate alloc;
+
 
 pub mod collections;
 pub mod containers;
+pub(crate) mod hash;
 pub(crate) mod macros;
+pub mod reporting;
 pub mod utils;
 
 
@@ -0,0 +1,725 @@
+// reporting/frequency_report.rs : `FrequencyReport`
+
+use crate::containers::{
+    FrequencyMap,
+    UnicodePointMap,
+};
+
+use alloc::{
+    format,
+    string::{
+        String,
+        ToString as _,
+    },
+    vec::Vec,
+};
+
+use core::{
+    cmp as std_cmp,
+    fmt as std_fmt,
+    fmt::Write as _,
+};
+
+#[cfg(feature = "std")]
+use std::io as std_io;
+
+
+/// The order in which the records of a [`FrequencyReport`] are rendered.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub enum SortOrder {
+    /// Highest count first; ties are ordered by ascending key.
+    #[default]
+    CountDescending,
+    /// Lowest count first; ties are ordered by ascending key.
+    CountAscending,
+    /// Ascending key order.
+    KeyAscending,
+    /// Descending key order.
+    KeyDescending,
+}
+
+/// The characters with which bar charts are drawn.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub enum BarStyle {
+    /// Bars are drawn with `#`, to whole-character resolution.
+    #[default]
+    Ascii,
+    /// Bars are drawn with Unicode block elements, to one-eighth-character
+    /// resolution.
+    Unicode,
+}
+
+/// Options that control the rendering of a [`FrequencyReport`].
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct ReportOptions {
+    /// The order in which records are rendered.
+    pub sort_order :      SortOrder,
+    /// If `Some(n)`, only the first `n` records (after sorting) are
+    /// rendered.
+    pub top_n :           Option<usize>,
+    /// Whether each record's percentage of the total is rendered.
+    pub show_percentage : bool,
+    /// Whether control and invisible characters in keys are rendered as
+    /// escape sequences (e.g. `\n`, `\u{200b}`).
+    pub escape_keys :     bool,
+    /// If `Some(n)`, keys longer than `n` characters are truncated.
+    pub max_key_width :   Option<usize>,
+    /// The length, in characters, of the longest bar in a bar chart.
+    pub bar_width :       usize,
+    /// The characters with which bar charts are drawn.
+    pub bar_style :       BarStyle,
+}
+
+impl Default for ReportOptions {
+    /// Creates an instance that sorts by descending count, shows
+    /// percentages, escapes keys, and draws ASCII bars of up to 40
+    /// characters.
+    fn default() -> Self {
+        Self {
+            sort_order :      SortOrder::default(),
+            top_n :           None,
+            show_percentage : true,
+            escape_keys :     true,
+            max_key_width :   None,
+            bar_width :       40,
+            bar_style :       BarStyle::default(),
+        }
+    }
+}
+
+
+/// A snapshot of the records of a [`FrequencyMap`] or
+/// [`UnicodePointMap`] that may be rendered as an aligned text table or as
+/// a horizontal bar chart.
+///
+/// # Examples
+///
+/// ```
+/// use collect_rs::{
+///     containers::FrequencyMap,
+///     reporting::{
+///         FrequencyReport,
+///         ReportOptions,
+///     },
+/// };
+///
+/// let fm = FrequencyMap::from(["cat", "dog", "dog"]);
+///
+/// let mut s = String::new();
+///
+/// FrequencyReport::from(&fm).write_table(&mut s, &ReportOptions::default()).unwrap();
+///
+/// assert_eq!("key  count       %\ndog      2  66.67%\ncat      1  33.33%\n", s);
+/// ```
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct FrequencyReport<K> {
+    /// The key-count pairs.
+    records : Vec<(K, isize)>,
+    /// The total frequency count of the source container.
+    total :   isize,
+}
+
+// API functions
+
+impl<K> FrequencyReport<K> {
+    /// Creates an instance from the given key-count pairs and `total`,
+    /// the latter being used as the denominator for percentages.
+    pub fn new(
+        records : Vec<(K, isize)>,
+        total : isize,
+    ) -> Self {
+        Self {
+            records,
+            total,
+        }
+    }
+}
+
+// Non-mutating methods
+
+impl<K : std_fmt::Display + Ord> FrequencyReport<K> {
+    /// Writes the records as a horizontal bar chart to the given
+    /// [`core::fmt::Write`] instance.
+    ///
+    /// Bar lengths are proportional to the largest count rendered;
+    /// non-positive counts have no bar.
+    pub fn write_bar_chart<W : std_fmt::Write>(
+        &self,
+        w : &mut W,
+        options : &ReportOptions,
+    ) -> std_fmt::Result {
+        let rows = self.rows_(options);
+        let key_width = rows.iter().map(|row| row.key.chars().count()).max().unwrap_or(0);
+        let count_width = rows.iter().map(|row| row.count.len()).max().unwrap_or(0);
+        let max_count = rows.iter().map(|row| row.value).max().unwrap_or(0);
+
+        for row in &rows {
+            write_padded_(w, &row.key, key_width, false)?;
+            w.write_str(" | ")?;
+
+            let bar = render_bar_(row.value, max_count, options.bar_width, options.bar_style);
+
+            write_padded_(w, &bar, options.bar_width, false)?;
+            w.write_char(' ')?;
+            write_padded_(w, &row.count, count_width, true)?;
+
+            if let Some(percentage) = &row.percentage {
+                write!(w, " ({percentage})")?;
+            }
+
+            w.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the records as a horizontal bar chart to the given
+    /// [`std::io::Write`] instance.
+    #[cfg(feature = "std")]
+    pub fn write_bar_chart_io<W : std_io::Write>(
+        &self,
+        w : &mut W,
+        options : &ReportOptions,
+    ) -> std_io::Result<()> {
+        let mut adapter = IoWriteAdapter::new(w);
+
+        self.write_bar_chart(&mut adapter, options).map_err(|_| adapter.into_error_())
+    }
+
+    /// Writes the records as an aligned text table, with a header row, to
+    /// the given [`core::fmt::Write`] instance.
+    pub fn write_table<W : std_fmt::Write>(
+        &self,
+        w : &mut W,
+        options : &ReportOptions,
+    ) -> std_fmt::Result {
+        const KEY_HEADING : &str = "key";
+        const COUNT_HEADING : &str = "count";
+        const PERCENTAGE_HEADING : &str = "%";
+
+        let rows = self.rows_(options);
+        let key_width = rows
+            .iter()
+            .map(|row| row.key.chars().count())
+            .chain(Some(KEY_HEADING.len()))
+            .max()
+            .unwrap_or(0);
+        let count_width = rows
+            .iter()
+            .map(|row| row.count.len())
+            .chain(Some(COUNT_HEADING.len()))
+            .max()
+            .unwrap_or(0);
+        let percentage_width = rows
+            .iter()
+            .filter_map(|row| row.percentage.as_ref().map(String::len))
+            .chain(Some(PERCENTAGE_HEADING.len()))
+            .max()
+            .unwrap_or(0);
+
+        write_padded_(w, KEY_HEADING, key_width, false)?;
+        w.write_str("  ")?;
+        write_padded_(w, COUNT_HEADING, count_width, true)?;
+        if options.show_percentage {
+            w.write_str("  ")?;
+            write_padded_(w, PERCENTAGE_HEADING, percentage_width, true)?;
+        }
+        w.write_char('\n')?;
+
+        for row in &rows {
+            write_padded_(w, &row.key, key_width, false)?;
+            w.write_str("  ")?;
+            write_padded_(w, &row.count, count_width, true)?;
+            if let Some(percentage) = &row.percentage {
+                w.write_str("  ")?;
+                write_padded_(w, percentage, percentage_width, true)?;
+            }
+            w.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the records as an aligned text table, with a header row, to
+    /// the given [`std::io::Write`] instance.
+    #[cfg(feature = "std")]
+    pub fn write_table_io<W : std_io::Write>(
+        &self,
+        w : &mut W,
+        options : &ReportOptions,
+    ) -> std_io::Result<()> {
+        let mut adapter = IoWriteAdapter::new(w);
+
+        self.write_table(&mut adapter, options).map_err(|_| adapter.into_error_())
+    }
+}
+
+impl<K> FrequencyReport<K> {
+    /// Indicates whether the report contains no records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Obtains the number of records.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Indicates the total frequency count of the source container.
+    #[inline]
+    pub fn total(&self) -> isize {
+        self.total
+    }
+}
+
+// Implementation
+
+/// Adapts a [`std::io::Write`] instance to [`core::fmt::Write`], so that
+/// rendering writes straight through to it, retaining the I/O error (if
+/// any) that [`core::fmt::Error`] cannot carry.
+#[cfg(feature = "std")]
+struct IoWriteAdapter<'a, W : std_io::Write> {
+    w :     &'a mut W,
+    error : Option<std_io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, W : std_io::Write> IoWriteAdapter<'a, W> {
+    fn new(w : &'a mut W) -> Self {
+        Self {
+            w,
+            error : None,
+        }
+    }
+
+    /// Obtains the retained I/O error, or, if the failure arose in the
+    /// rendering rather than in the writing, an error describing that.
+    fn into_error_(self) -> std_io::Error {
+        self.error.unwrap_or_else(|| std_io::Error::other("formatting error"))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W : std_io::Write> std_fmt::Write for IoWriteAdapter<'_, W> {
+    fn write_str(
+        &mut self,
+        s : &str,
+    ) -> std_fmt::Result {
+        self.w.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+
+            std_fmt::Error
+        })
+    }
+}
+
+/// A record rendered to strings, ready for alignment.
+struct Row {
+    key :        String,
+    value :      isize,
+    count :      String,
+    percentage : Option<String>,
+}
+
+impl<K : std_fmt::Display + Ord> FrequencyReport<K> {
+    fn rows_(
+        &self,
+        options : &ReportOptions,
+    ) -> Vec<Row> {
+        let mut records : Vec<_> = self.records.iter().collect();
+
+        match options.sort_order {
+            SortOrder::CountDescending => {
+                records.sort_by(|(k1, c1), (k2, c2)| c2.cmp(c1).then_with(|| k1.cmp(k2)));
+            },
+            SortOrder::CountAscending => {
+                records.sort_by(|(k1, c1), (k2, c2)| c1.cmp(c2).then_with(|| k1.cmp(k2)));
+            },
+            SortOrder::KeyAscending => {
+                records.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+            },
+            SortOrder::KeyDescending => {
+                records.sort_by(|(k1, _), (k2, _)| std_cmp::Reverse(k1).cmp(&std_cmp::Reverse(k2)));
+            },
+        }
+
+        if let Some(top_n) = options.top_n {
+            records.truncate(top_n);
+        }
+
+        records
+            .into_iter()
+            .map(|(key, count)| {
+                let key = key.to_string();
+                let key = if options.escape_keys { escape_key_(&key) } else { key };
+                let key = match options.max_key_width {
+                    Some(max_key_width) => truncate_key_(key, max_key_width),
+                    None => key,
+                };
+                let percentage = if options.show_percentage {
+                    let percentage = if 0 == self.total {
+                        0.0
+                    } else {
+                        100.0 * *count as f64 / self.total as f64
+                    };
+
+                    Some(format!("{percentage:.2}%"))
+                } else {
+                    None
+                };
+
+                Row {
+                    key,
+                    value : *count,
+                    count : count.to_string(),
+                    percentage,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Indicates whether `c` would not be visible if written as-is.
+fn is_invisible_(c : char) -> bool {
+    c.is_control()
+        || (c.is_whitespace() && ' ' != c)
+        || matches!(c, '\u{ad}' | '\u{200b}'..='\u{200f}' | '\u{2060}'..='\u{2064}' | '\u{feff}')
+}
+
+/// Escapes control and invisible characters, along with any leading or
+/// trailing spaces, so that the key remains visible in aligned columns.
+fn escape_key_(key : &str) -> String {
+    let first_non_space = key.find(|c| ' ' != c).unwrap_or(key.len());
+    let last_non_space = key.rfind(|c| ' ' != c).map_or(first_non_space, |ix| ix + 1);
+
+    let mut s = String::with_capacity(key.len());
+
+    for (ix, c) in key.char_indices() {
+        if ' ' == c && (ix < first_non_space || ix >= last_non_space) {
+            s.push_str("\\u{20}");
+        } else if is_invisible_(c) {
+            match c {
+                '\n' => s.push_str("\\n"),
+                '\r' => s.push_str("\\r"),
+                '\t' => s.push_str("\\t"),
+                _ => {
+                    let _ = write!(s, "\\u{{{:x}}}", c as u32);
+                },
+            }
+        } else {
+            s.push(c);
+        }
+    }
+
+    s
+}
+
+fn render_bar_(
+    value : isize,
+    max_value : isize,
+    bar_width : usize,
+    bar_style : BarStyle,
+) -> String {
+    if value <= 0 || max_value <= 0 {
+        return String::new();
+    }
+
+    let fraction = value as f64 / max_value as f64;
+
+    match bar_style {
+        BarStyle::Ascii => {
+            let n = (fraction * bar_width as f64).round() as usize;
+
+            "#".repeat(n)
+        },
+        BarStyle::Unicode => {
+            const PARTIALS : [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+            let eighths = (fraction * (bar_width * 8) as f64).round() as usize;
+
+            let partial = eighths % 8;
+
+            let mut s = "█".repeat(eighths / 8);
+
+            if 0 != partial {
+                s.push(PARTIALS[partial]);
+            }
+
+            s
+        },
+    }
+}
+
+fn truncate_key_(
+    key : String,
+    max_key_width : usize,
+) -> String {
+    const ELLIPSIS : &str = "...";
+
+    if key.chars().count() <= max_key_width {
+        key
+    } else if max_key_width <= ELLIPSIS.len() {
+        key.chars().take(max_key_width).collect()
+    } else {
+        let mut s : String = key.chars().take(max_key_width - ELLIPSIS.len()).collect();
+
+        s.push_str(ELLIPSIS);
+
+        s
+    }
+}
+
+fn write_padded_<W : std_fmt::Write>(
+    w : &mut W,
+    s : &str,
+    width : usize,
+    right_align : bool,
+) -> std_fmt::Result {
+    let padding = width.saturating_sub(s.chars().count());
+
+    if right_align {
+        write!(w, "{:padding$}{s}", "")
+    } else {
+        write!(w, "{s}{:padding$}", "")
+    }
+}
+
+// Trait implementations
+
+impl<'a, K> From<&'a FrequencyMap<K>> for FrequencyReport<&'a K> {
+    /// Creates an instance from the records of a [`FrequencyMap`].
+    fn from(value: &'a FrequencyMap<K>) -> Self {
+        let records = value.iter().map(|(key, &count)| (key, count)).collect();
+        let total = value.total();
+
+        Self {
+            records,
+            total,
+        }
+    }
+}
+
+impl From<&UnicodePointMap> for FrequencyReport<char> {
+    /// Creates an instance from the records of a [`UnicodePointMap`].
+    fn from(value: &UnicodePointMap) -> Self {
+        let records = value.iter().collect();
+        let total = value.total() as isize;
+
+        Self {
+            records,
+            total,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::{
+        BarStyle,
+        FrequencyReport,
+        ReportOptions,
+        SortOrder,
+    };
+
+    use crate::containers::{
+        FrequencyMap,
+        UnicodePointMap,
+    };
+
+
+    #[test]
+    fn TEST_FrequencyReport_write_table_1() {
+        let fm = FrequencyMap::<&str>::from([
+            // insert list
+            ("the", 5),
+            ("cat", 2),
+            ("sat", 2),
+            ("mat", 1),
+        ]);
+
+        let report = FrequencyReport::from(&fm);
+
+        assert_eq!(4, report.len());
+        assert_eq!(10, report.total());
+
+        let mut s = String::new();
+
+        report.write_table(&mut s, &ReportOptions::default()).unwrap();
+
+        assert_eq!(
+            "\
+key  count       %
+the      5  50.00%
+cat      2  20.00%
+sat      2  20.00%
+mat      1  10.00%
+",
+            s
+        );
+    }
+
+    #[test]
+    fn TEST_FrequencyReport_write_table_2() {
+        let fm = FrequencyMap::<&str>::from([
+            // insert list
+            ("the", 5),
+            ("cat", 2),
+            ("sat", 2),
+            ("mat", 1),
+        ]);
+
+        let options = ReportOptions {
+            sort_order : SortOrder::KeyDescending,
+            top_n : Some(2),
+            show_percentage : false,
+            ..Default::default()
+        };
+
+        let mut s = String::new();
+
+        FrequencyReport::from(&fm).write_table(&mut s, &options).unwrap();
+
+        assert_eq!("key  count\nthe      5\nsat      2\n", s);
+    }
+
+    #[test]
+    fn TEST_FrequencyReport_write_table_ESCAPING() {
+        let upm = UnicodePointMap::from([
+            // insert list
+            ('a', 3),
+            ('\n', 2),
+            (' ', 1),
+            ('\u{200b}', 1),
+        ]);
+
+        let options = ReportOptions {
+            sort_order : SortOrder::KeyAscending,
+            show_percentage : false,
+            ..Default::default()
+        };
+
+        let mut s = String::new();
+
+        FrequencyReport::from(&upm).write_table(&mut s, &options).unwrap();
+
+        assert_eq!(
+            "\
+key       count
+\\n            2
+\\u{20}        1
+a             3
+\\u{200b}      1
+",
+            s
+        );
+    }
+
+    #[test]
+    fn TEST_FrequencyReport_write_table_TRUNCATION() {
+        let fm = FrequencyMap::from(["internationalisation", "i18n"]);
+
+        let options = ReportOptions {
+            sort_order : SortOrder::KeyAscending,
+            show_percentage : false,
+            max_key_width : Some(8),
+            ..Default::default()
+        };
+
+        let mut s = String::new();
+
+        FrequencyReport::from(&fm).write_table(&mut s, &options).unwrap();
+
+        assert_eq!("key       count\ni18n          1\ninter...      1\n", s);
+    }
+
+    #[test]
+    fn TEST_FrequencyReport_write_bar_chart_ASCII() {
+        let fm = FrequencyMap::<&str>::from([
+            // insert list
+            ("a", 4),
+            ("bb", 2),
+            ("c", 1),
+        ]);
+
+        let options = ReportOptions {
+            show_percentage : false,
+            bar_width : 8,
+            ..Default::default()
+        };
+
+        let mut s = String::new();
+
+        FrequencyReport::from(&fm).write_bar_chart(&mut s, &options).unwrap();
+
+        assert_eq!("a  | ######## 4\nbb | ####     2\nc  | ##       1\n", s);
+    }
+
+    #[test]
+    fn TEST_FrequencyReport_write_bar_chart_UNICODE() {
+        let fm = FrequencyMap::<&str>::from([
+            // insert list
+            ("a", 8),
+            ("b", 3),
+        ]);
+
+        let options = ReportOptions {
+            bar_width : 2,
+            bar_style : BarStyle::Unicode,
+            ..Default::default()
+        };
+
+        let mut s = String::new();
+
+        FrequencyReport::from(&fm).write_bar_chart(&mut s, &options).unwrap();
+
+        assert_eq!("a | ██ 8 (72.73%)\nb | ▊  3 (27.27%)\n", s);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn TEST_FrequencyReport_write_table_io_1() {
+        let fm = FrequencyMap::from(["x"]);
+
+        let mut v = Vec::new();
+
+        FrequencyReport::from(&fm).write_table_io(&mut v, &ReportOptions::default()).unwrap();
+
+        assert_eq!(b"key  count        %\nx        1  100.00%\n", v.as_slice());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn TEST_FrequencyReport_write_bar_chart_io_PROPAGATES_WRITE_ERROR() {
+        let fm = FrequencyMap::from(["x", "y", "y"]);
+
+        let mut buf = [0u8; 8];
+        let mut w = &mut buf[..];
+
+        let e = FrequencyReport::from(&fm).write_bar_chart_io(&mut w, &ReportOptions::default()).unwrap_err();
+
+        assert_eq!(std::io::ErrorKind::WriteZero, e.kind());
+        // written through as rendered, rather than all at once at the end
+        assert_eq!(b"y | ####", &buf);
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
@@ -0,0 +1,446 @@
+// reporting/keyness.rs : `KeynessReport`
+
+use crate::{
+    containers::FrequencyMap,
+    hash::HashMap,
+};
+
+use core::{
+    cmp as std_cmp,
+    hash as std_hash,
+};
+
+
+/// The measure by which the records of a [`KeynessReport`] are ranked.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub enum KeynessMeasure {
+    /// Descending log-likelihood (G²), signed such that keys that are
+    /// relatively more frequent in the study corpus rank first, and those
+    /// relatively less frequent rank last.
+    #[default]
+    LogLikelihood,
+    /// Descending z-score of the log-odds ratio with an informative
+    /// Dirichlet prior.
+    LogOdds,
+    /// Descending %DIFF.
+    PercentDiff,
+}
+
+/// Options that control the creation of a [`KeynessReport`].
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct KeynessOptions {
+    /// The measure by which records are ranked.
+    pub rank_by :             KeynessMeasure,
+    /// Keys whose combined count, in the study and reference corpora, is
+    /// less than this are omitted.
+    pub min_frequency :       isize,
+    /// Keys whose count in the study corpus is less than this are omitted.
+    pub min_study_frequency : isize,
+    /// The total weight, `α₀`, of the informative Dirichlet prior of the
+    /// log-odds ratio, which is distributed between keys in proportion to
+    /// their combined counts. If `None`, the combined total of the two
+    /// corpora is used, i.e. the prior for each key is its combined count.
+    pub prior_scale :         Option<f64>,
+}
+
+impl Default for KeynessOptions {
+    /// Creates an instance that ranks by log-likelihood, omits no keys,
+    /// and uses the combined corpora as the log-odds prior.
+    fn default() -> Self {
+        Self {
+            rank_by :             KeynessMeasure::default(),
+            min_frequency :       1,
+            min_study_frequency : 0,
+            prior_scale :         None,
+        }
+    }
+}
+
+/// The keyness statistics of a single key, as obtained from a
+/// [`KeynessReport`].
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct KeynessRecord<K> {
+    /// The key.
+    pub key :             K,
+    /// The count of the key in the study corpus.
+    pub study_count :     isize,
+    /// The count of the key in the reference corpus.
+    pub reference_count : isize,
+    /// The log-likelihood statistic (G²), which is non-negative, and is
+    /// greater the more significant the difference in relative frequency
+    /// between the corpora.
+    pub log_likelihood :  f64,
+    /// The log-odds ratio, with an informative Dirichlet prior, of the key
+    /// in the study corpus relative to the reference corpus, which is
+    /// positive if the key is relatively more frequent in the study
+    /// corpus.
+    pub log_odds :        f64,
+    /// The z-score of `log_odds`, i.e. it divided by its estimated standard
+    /// deviation.
+    pub log_odds_z :      f64,
+    /// The %DIFF, i.e. the percentage by which the relative frequency of
+    /// the key in the study corpus exceeds that in the reference corpus,
+    /// which is -100 for a key absent from the study corpus and infinite
+    /// for a key absent from the reference corpus.
+    pub percent_diff :    f64,
+}
+
+impl<K> KeynessRecord<K> {
+    /// Indicates whether the key is relatively more frequent in the study
+    /// corpus than in the reference corpus.
+    #[inline]
+    pub fn is_overused(&self) -> bool {
+        self.percent_diff > 0.0
+    }
+
+    /// Obtains the log-likelihood statistic (G²), negated if the key is
+    /// relatively less frequent in the study corpus.
+    #[inline]
+    pub fn signed_log_likelihood(&self) -> f64 {
+        if self.percent_diff < 0.0 {
+            -self.log_likelihood
+        } else {
+            self.log_likelihood
+        }
+    }
+}
+
+
+/// A comparison of the keys of a study corpus and a reference corpus, each
+/// represented by a [`FrequencyMap`], by the keyness statistics
+/// log-likelihood (G²), log-odds ratio with an informative Dirichlet
+/// prior, and %DIFF, ranked according to [`KeynessOptions::rank_by`].
+///
+/// Records with non-positive counts are ignored, and the corpus totals are
+/// the sums of the positive counts.
+///
+/// # Examples
+///
+/// ```
+/// use collect_rs::{
+///     containers::FrequencyMap,
+///     reporting::{
+///         KeynessOptions,
+///         KeynessReport,
+///     },
+/// };
+///
+/// let study = FrequencyMap::from([("whale", 40), ("the", 500), ("ship", 30)]);
+/// let reference = FrequencyMap::from([("whale", 2), ("the", 600), ("ship", 20), ("house", 40)]);
+///
+/// let report = KeynessReport::new(&study, &reference, &KeynessOptions::default());
+///
+/// assert_eq!(Some(&"whale"), report.iter().next().map(|record| record.key));
+/// assert_eq!(Some(&"house"), report.iter().last().map(|record| record.key));
+/// ```
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct KeynessReport<K> {
+    /// The records, in rank order.
+    records :         Vec<KeynessRecord<K>>,
+    /// The total of the study corpus.
+    study_total :     i64,
+    /// The total of the reference corpus.
+    reference_total : i64,
+}
+
+// API functions
+
+impl<'a, K> KeynessReport<&'a K>
+where
+    K : std_cmp::Ord + std_hash::Hash,
+{
+    /// Creates an instance comparing the keys of `study` with those of
+    /// `reference`, filtered and ranked according to `options`.
+    ///
+    /// Records of equal rank are ordered by ascending key.
+    pub fn new(
+        study : &'a FrequencyMap<K>,
+        reference : &'a FrequencyMap<K>,
+        options : &KeynessOptions,
+    ) -> Self {
+        let mut counts : HashMap<&'a K, (isize, isize)> = HashMap::new();
+
+        for (key, &count) in study.iter().filter(|(_, &count)| count > 0) {
+            counts.entry(key).or_default().0 = count;
+        }
+
+        for (key, &count) in reference.iter().filter(|(_, &count)| count > 0) {
+            counts.entry(key).or_default().1 = count;
+        }
+
+        let study_total : i64 = counts.values().map(|&(a, _)| a as i64).sum();
+        let reference_total : i64 = counts.values().map(|&(_, b)| b as i64).sum();
+
+        let c = study_total as f64;
+        let d = reference_total as f64;
+        let prior_scale = options.prior_scale.unwrap_or(c + d);
+
+        let mut records : Vec<_> = counts
+            .into_iter()
+            .filter(|&(_, (a, b))| a + b >= options.min_frequency && a >= options.min_study_frequency)
+            .map(|(key, (a, b))| {
+                let (log_odds, log_odds_z) = log_odds_(a as f64, b as f64, c, d, prior_scale);
+
+                KeynessRecord {
+                    key,
+                    study_count : a,
+                    reference_count : b,
+                    log_likelihood : log_likelihood_(a as f64, b as f64, c, d),
+                    log_odds,
+                    log_odds_z,
+                    percent_diff : percent_diff_(a as f64, b as f64, c, d),
+                }
+            })
+            .collect();
+
+        let measure : fn(&KeynessRecord<&'a K>) -> f64 = match options.rank_by {
+            KeynessMeasure::LogLikelihood => KeynessRecord::signed_log_likelihood,
+            KeynessMeasure::LogOdds => |record| record.log_odds_z,
+            KeynessMeasure::PercentDiff => |record| record.percent_diff,
+        };
+
+        records.sort_by(|lhs, rhs| measure(rhs).total_cmp(&measure(lhs)).then_with(|| lhs.key.cmp(rhs.key)));
+
+        Self {
+            records,
+            study_total,
+            reference_total,
+        }
+    }
+}
+
+// Non-mutating methods
+
+impl<K> KeynessReport<K> {
+    /// Indicates whether the instance contains no records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// An iterator visiting all records in rank order. The iterator
+    /// element type is `&'a KeynessRecord<K>`.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &KeynessRecord<K>> {
+        self.records.iter()
+    }
+
+    /// Obtains the number of records.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Obtains the records in rank order.
+    #[inline]
+    pub fn records(&self) -> &[KeynessRecord<K>] {
+        &self.records
+    }
+
+    /// Indicates the total of the positive counts of the reference corpus.
+    #[inline]
+    pub fn reference_total(&self) -> i64 {
+        self.reference_total
+    }
+
+    /// Indicates the total of the positive counts of the study corpus.
+    #[inline]
+    pub fn study_total(&self) -> i64 {
+        self.study_total
+    }
+}
+
+
+// Implementation
+
+/// Obtains the log-likelihood statistic (G²) of a key with counts `a` and
+/// `b` in corpora with totals `c` and `d`, per Rayson & Garside (2000).
+fn log_likelihood_(
+    a : f64,
+    b : f64,
+    c : f64,
+    d : f64,
+) -> f64 {
+    let term = |observed : f64, expected : f64| {
+        if 0.0 == observed {
+            0.0
+        } else {
+            observed * (observed / expected).ln()
+        }
+    };
+
+    let e1 = c * (a + b) / (c + d);
+    let e2 = d * (a + b) / (c + d);
+
+    (2.0 * (term(a, e1) + term(b, e2))).max(0.0)
+}
+
+/// Obtains the log-odds ratio, and its z-score, of a key with counts `a`
+/// and `b` in corpora with totals `c` and `d`, with an informative
+/// Dirichlet prior of total weight `prior_scale` distributed in proportion
+/// to the combined counts, per Monroe, Colaresi & Quinn (2008).
+fn log_odds_(
+    a : f64,
+    b : f64,
+    c : f64,
+    d : f64,
+    prior_scale : f64,
+) -> (f64, f64) {
+    let alpha_w = prior_scale * (a + b) / (c + d);
+
+    let delta = ((a + alpha_w) / (c + prior_scale - a - alpha_w)).ln() - ((b + alpha_w) / (d + prior_scale - b - alpha_w)).ln();
+    let variance = 1.0 / (a + alpha_w) + 1.0 / (b + alpha_w);
+
+    (delta, delta / variance.sqrt())
+}
+
+/// Obtains the %DIFF of a key with counts `a` and `b` in corpora with
+/// totals `c` and `d`, per Gabrielatos & Marchi (2012).
+fn percent_diff_(
+    a : f64,
+    b : f64,
+    c : f64,
+    d : f64,
+) -> f64 {
+    if 0.0 == b {
+        f64::INFINITY
+    } else if 0.0 == a {
+        -100.0
+    } else {
+        100.0 * (a / c - b / d) / (b / d)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::{
+        KeynessMeasure,
+        KeynessOptions,
+        KeynessReport,
+    };
+
+    use crate::containers::FrequencyMap;
+
+
+    fn make_corpora() -> (FrequencyMap<&'static str>, FrequencyMap<&'static str>) {
+        let study = FrequencyMap::from([
+            // insert list
+            ("target", 100),
+            ("common", 800),
+            ("rare", 2),
+            ("study-only", 98),
+        ]);
+        let reference = FrequencyMap::from([
+            // insert list
+            ("target", 50),
+            ("common", 800),
+            ("rare", 1),
+            ("reference-only", 149),
+            ("negative", -5),
+        ]);
+
+        (study, reference)
+    }
+
+    #[test]
+    fn TEST_KeynessReport_STATISTICS() {
+        let (study, reference) = make_corpora();
+
+        let report = KeynessReport::new(&study, &reference, &KeynessOptions::default());
+
+        assert_eq!(5, report.len());
+        assert_eq!(1000, report.study_total());
+        assert_eq!(1000, report.reference_total());
+
+        let target = report.iter().find(|record| *record.key == "target").unwrap();
+
+        assert_eq!(100, target.study_count);
+        assert_eq!(50, target.reference_count);
+        assert!((16.98993 - target.log_likelihood).abs() < 1e-4, "{}", target.log_likelihood);
+        assert!((0.241162 - target.log_odds).abs() < 1e-5, "{}", target.log_odds);
+        assert!((2.54208 - target.log_odds_z).abs() < 1e-4, "{}", target.log_odds_z);
+        assert!((100.0 - target.percent_diff).abs() < 1e-9);
+        assert!(target.is_overused());
+
+        let common = report.iter().find(|record| *record.key == "common").unwrap();
+
+        assert!(common.log_likelihood.abs() < 1e-9);
+        assert!(common.log_odds.abs() < 1e-9);
+        assert!(common.percent_diff.abs() < 1e-9);
+
+        let reference_only = report.iter().find(|record| *record.key == "reference-only").unwrap();
+
+        assert_eq!(-100.0, reference_only.percent_diff);
+        assert!(!reference_only.is_overused());
+        assert!(reference_only.signed_log_likelihood() < 0.0);
+
+        let study_only = report.iter().find(|record| *record.key == "study-only").unwrap();
+
+        assert_eq!(f64::INFINITY, study_only.percent_diff);
+        assert!(!report.iter().any(|record| *record.key == "negative"));
+
+        let empty = FrequencyMap::new();
+        let report = KeynessReport::new(&empty, &reference, &KeynessOptions::default());
+
+        assert_eq!(4, report.len());
+        assert!(report.iter().all(|record| -100.0 == record.percent_diff && record.log_likelihood.is_finite()));
+    }
+
+    #[test]
+    fn TEST_KeynessReport_RANKING_AND_FILTERING() {
+        let (study, reference) = make_corpora();
+
+        let keys = |options : &KeynessOptions| KeynessReport::new(&study, &reference, options).iter().map(|record| *record.key).collect::<Vec<_>>();
+
+        assert_eq!(vec!["study-only", "target", "rare", "common", "reference-only"], keys(&KeynessOptions::default()));
+
+        assert_eq!(
+            vec!["study-only", "target", "common", "reference-only"],
+            keys(&KeynessOptions {
+                min_frequency : 4,
+                ..Default::default()
+            })
+        );
+
+        assert_eq!(
+            vec!["study-only", "target", "rare", "common"],
+            keys(&KeynessOptions {
+                min_study_frequency : 1,
+                ..Default::default()
+            })
+        );
+
+        let by_percent_diff = keys(&KeynessOptions {
+            rank_by : KeynessMeasure::PercentDiff,
+            ..Default::default()
+        });
+
+        assert_eq!("study-only", by_percent_diff[0]);
+        assert_eq!("reference-only", by_percent_diff[4]);
+
+        let by_log_odds = keys(&KeynessOptions {
+            rank_by : KeynessMeasure::LogOdds,
+            prior_scale : Some(100.0),
+            ..Default::default()
+        });
+
+        assert_eq!(5, by_log_odds.len());
+        assert_eq!("reference-only", by_log_odds[4]);
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
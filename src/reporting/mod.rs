@@ -0,0 +1,14 @@
+// reporting/mod.rs
+
+use crate::macros::declare_and_publish;
+
+
+declare_and_publish!(frequency_report, BarStyle, FrequencyReport, ReportOptions, SortOrder);
+#[cfg(feature = "std")]
+declare_and_publish!(keyness, KeynessMeasure, KeynessOptions, KeynessRecord, KeynessReport);
+#[cfg(feature = "std")]
+declare_and_publish!(rank_frequency, PowerLawFit, PowerLawOptions, RankFrequencyCurve);
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
@@ -0,0 +1,440 @@
+// reporting/rank_frequency.rs : `RankFrequencyCurve`, `PowerLawFit`
+
+use crate::containers::{
+    FrequencyMap,
+    UnicodePointMap,
+};
+
+
+/// The lower bound of the range in which the exponent of a power law is
+/// sought.
+const ALPHA_LOWER_BOUND : f64 = 1.0 + 1e-6;
+/// The upper bound of the range in which the exponent of a power law is
+/// sought.
+const ALPHA_UPPER_BOUND : f64 = 20.0;
+/// The width of the range within which the golden-section search for the
+/// exponent of a power law terminates.
+const ALPHA_TOLERANCE : f64 = 1e-9;
+
+
+/// Options that control [`RankFrequencyCurve::fit_power_law()`].
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct PowerLawOptions {
+    /// The minimum number of records that must have a count of at least
+    /// `x_min` for `x_min` to be considered.
+    pub min_tail_len : usize,
+    /// If `Some(n)`, values of `x_min` greater than `n` are not considered.
+    pub max_x_min :    Option<u64>,
+}
+
+impl Default for PowerLawOptions {
+    /// Creates an instance that requires a tail of at least 10 records and
+    /// places no upper bound on `x_min`.
+    fn default() -> Self {
+        Self {
+            min_tail_len : 10,
+            max_x_min :    None,
+        }
+    }
+}
+
+/// The result of fitting a discrete power law, `p(x) ∝ x^-alpha` for
+/// `x >= x_min`, to the counts of a [`RankFrequencyCurve`], as obtained
+/// from [`RankFrequencyCurve::fit_power_law()`].
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct PowerLawFit {
+    /// The maximum-likelihood estimate of the exponent.
+    pub alpha :          f64,
+    /// The count above which the power law is fitted, being that which
+    /// minimises `ks_statistic`.
+    pub x_min :          u64,
+    /// The number of records whose count is at least `x_min`.
+    pub tail_len :       usize,
+    /// The Kolmogorov-Smirnov statistic, i.e. the maximum distance between
+    /// the empirical and fitted cumulative distributions of the counts of
+    /// the tail, as a measure of goodness of fit; smaller is better.
+    pub ks_statistic :   f64,
+    /// The log-likelihood of the counts of the tail under the fitted law.
+    pub log_likelihood : f64,
+}
+
+impl PowerLawFit {
+    /// Obtains the exponent `s` of the equivalent Zipf law, `f(r) ∝ r^-s`,
+    /// relating frequency to rank, which is `1 / (alpha - 1)`.
+    #[inline]
+    pub fn zipf_exponent(&self) -> f64 {
+        1.0 / (self.alpha - 1.0)
+    }
+}
+
+
+/// The rank-frequency curve of the records of a [`FrequencyMap`] or
+/// [`UnicodePointMap`], i.e. their positive counts in descending order,
+/// to which a power law may be fitted, and from which log-log points may
+/// be obtained for plotting.
+///
+/// Records with non-positive counts are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use collect_rs::{
+///     containers::FrequencyMap,
+///     reporting::{
+///         PowerLawOptions,
+///         RankFrequencyCurve,
+///     },
+/// };
+///
+/// // frequencies that follow Zipf's law, with s = 1
+/// let fm = FrequencyMap::<usize>::from_iter((1..=1000).flat_map(|r| std::iter::repeat_n(r, 10_000 / r)));
+///
+/// let curve = RankFrequencyCurve::from(&fm);
+/// let fit = curve.fit_power_law(&PowerLawOptions::default()).unwrap();
+///
+/// assert!((fit.alpha - 2.0).abs() < 0.1);
+/// assert!((fit.zipf_exponent() - 1.0).abs() < 0.1);
+/// ```
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub struct RankFrequencyCurve {
+    /// The positive counts, in descending order.
+    frequencies : Vec<u64>,
+}
+
+// API functions
+
+impl RankFrequencyCurve {
+    /// Creates an instance from the given counts, ignoring any that are
+    /// not positive.
+    pub fn from_counts<I : IntoIterator<Item = isize>>(counts : I) -> Self {
+        let mut frequencies : Vec<u64> = counts.into_iter().filter(|&count| count > 0).map(|count| count as u64).collect();
+
+        frequencies.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+
+        Self {
+            frequencies,
+        }
+    }
+}
+
+// Non-mutating methods
+
+impl RankFrequencyCurve {
+    /// Fits a discrete power law to the counts, by maximum-likelihood
+    /// estimation of the exponent for each candidate `x_min`, choosing the
+    /// `x_min` for which the Kolmogorov-Smirnov statistic is least (as
+    /// described by Clauset, Shalizi & Newman, 2009).
+    ///
+    /// Returns `None` if no candidate `x_min` satisfies `options`.
+    pub fn fit_power_law(
+        &self,
+        options : &PowerLawOptions,
+    ) -> Option<PowerLawFit> {
+        // the distinct counts, in ascending order, and the number of
+        // records having each
+        let mut groups : Vec<(u64, usize)> = Vec::new();
+
+        for &frequency in self.frequencies.iter().rev() {
+            match groups.last_mut() {
+                Some((value, n)) if *value == frequency => *n += 1,
+                _ => groups.push((frequency, 1)),
+            }
+        }
+
+        let min_tail_len = options.min_tail_len.max(1);
+
+        let mut best : Option<PowerLawFit> = None;
+        let mut tail_len = self.frequencies.len();
+        let mut tail_ln_sum : f64 = self.frequencies.iter().map(|&x| (x as f64).ln()).sum();
+
+        for (ix, &(x_min, n)) in groups.iter().enumerate() {
+            if tail_len < min_tail_len || options.max_x_min.is_some_and(|max_x_min| x_min > max_x_min) {
+                break;
+            }
+
+            let alpha = mle_alpha_(x_min, tail_len, tail_ln_sum);
+            let ks_statistic = ks_statistic_(&groups[ix..], tail_len, alpha, x_min);
+
+            if best.as_ref().is_none_or(|fit| ks_statistic < fit.ks_statistic) {
+                best = Some(PowerLawFit {
+                    alpha,
+                    x_min,
+                    tail_len,
+                    ks_statistic,
+                    log_likelihood : -negative_log_likelihood_(alpha, x_min, tail_len, tail_ln_sum),
+                });
+            }
+
+            tail_len -= n;
+            tail_ln_sum -= n as f64 * (x_min as f64).ln();
+        }
+
+        best
+    }
+
+    /// Obtains the frequencies, in descending order, such that the
+    /// frequency of rank `r` is at index `r - 1`.
+    #[inline]
+    pub fn frequencies(&self) -> &[u64] {
+        &self.frequencies
+    }
+
+    /// Indicates whether the instance contains no frequencies.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frequencies.is_empty()
+    }
+
+    /// Obtains the number of frequencies.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frequencies.len()
+    }
+
+    /// An iterator visiting the points of the curve on log-log axes, i.e.
+    /// `(log10(rank), log10(frequency))`, in ascending order of rank, for
+    /// plotting. The iterator element type is `(f64, f64)`.
+    pub fn log_log_points(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.frequencies
+            .iter()
+            .enumerate()
+            .map(|(ix, &frequency)| (((ix + 1) as f64).log10(), (frequency as f64).log10()))
+    }
+}
+
+// Trait implementations
+
+impl<K> From<&FrequencyMap<K>> for RankFrequencyCurve {
+    fn from(value : &FrequencyMap<K>) -> Self {
+        Self::from_counts(value.iter().map(|(_, &count)| count))
+    }
+}
+
+impl From<&UnicodePointMap> for RankFrequencyCurve {
+    fn from(value : &UnicodePointMap) -> Self {
+        Self::from_counts(value.iter().map(|(_, count)| count))
+    }
+}
+
+
+// Implementation
+
+/// Obtains the Hurwitz zeta function, `Σ (k + q)^-s` for `k >= 0`, for
+/// `s > 1` and `q > 0`, by Euler-Maclaurin summation.
+fn hurwitz_zeta_(
+    s : f64,
+    q : f64,
+) -> f64 {
+    /// The number of terms that are summed directly.
+    const N : usize = 12;
+    /// The Bernoulli numbers `B(2j) / (2j)!` for `j` in `[1, 6]`.
+    const COEFFICIENTS : [f64; 6] = [
+        1.0 / 12.0,
+        -1.0 / 720.0,
+        1.0 / 30_240.0,
+        -1.0 / 1_209_600.0,
+        1.0 / 47_900_160.0,
+        -691.0 / 1_307_674_368_000.0,
+    ];
+
+    let mut sum : f64 = (0..N).map(|k| (q + k as f64).powf(-s)).sum();
+
+    let a = q + N as f64;
+
+    sum += a.powf(1.0 - s) / (s - 1.0) + 0.5 * a.powf(-s);
+
+    // the term for `j` is `s (s + 1) ... (s + 2j - 2) a^(-s - 2j + 1)`
+    let mut term = s * a.powf(-s - 1.0);
+
+    for (j, coefficient) in COEFFICIENTS.iter().enumerate() {
+        let j = (j + 1) as f64;
+
+        sum += coefficient * term;
+        term *= (s + 2.0 * j - 1.0) * (s + 2.0 * j) / (a * a);
+    }
+
+    sum
+}
+
+/// Obtains the negative log-likelihood of `n` counts, of at least `x_min`
+/// and whose logarithms sum to `ln_sum`, under a discrete power law with
+/// exponent `alpha`.
+fn negative_log_likelihood_(
+    alpha : f64,
+    x_min : u64,
+    n : usize,
+    ln_sum : f64,
+) -> f64 {
+    n as f64 * hurwitz_zeta_(alpha, x_min as f64).ln() + alpha * ln_sum
+}
+
+/// Obtains the maximum-likelihood estimate of the exponent of a discrete
+/// power law, by golden-section search of the (convex) negative
+/// log-likelihood.
+fn mle_alpha_(
+    x_min : u64,
+    n : usize,
+    ln_sum : f64,
+) -> f64 {
+    let inv_phi = (5f64.sqrt() - 1.0) / 2.0;
+    let f = |alpha| negative_log_likelihood_(alpha, x_min, n, ln_sum);
+
+    let (mut lo, mut hi) = (ALPHA_LOWER_BOUND, ALPHA_UPPER_BOUND);
+    let mut c = hi - inv_phi * (hi - lo);
+    let mut d = lo + inv_phi * (hi - lo);
+    let (mut fc, mut fd) = (f(c), f(d));
+
+    while hi - lo > ALPHA_TOLERANCE {
+        if fc < fd {
+            hi = d;
+            d = c;
+            fd = fc;
+            c = hi - inv_phi * (hi - lo);
+            fc = f(c);
+        } else {
+            lo = c;
+            c = d;
+            fc = fd;
+            d = lo + inv_phi * (hi - lo);
+            fd = f(d);
+        }
+    }
+
+    (lo + hi) / 2.0
+}
+
+/// Obtains the Kolmogorov-Smirnov statistic of the `n` counts represented
+/// by `groups` - the distinct counts, in ascending order, each with the
+/// number of records having it - against a discrete power law with
+/// exponent `alpha` and lower bound `x_min`.
+fn ks_statistic_(
+    groups : &[(u64, usize)],
+    n : usize,
+    alpha : f64,
+    x_min : u64,
+) -> f64 {
+    let zeta_x_min = hurwitz_zeta_(alpha, x_min as f64);
+
+    let mut cumulative = 0;
+    let mut max_distance : f64 = 0.0;
+
+    for &(x, m) in groups {
+        cumulative += m;
+
+        let empirical = cumulative as f64 / n as f64;
+        let fitted = 1.0 - hurwitz_zeta_(alpha, (x + 1) as f64) / zeta_x_min;
+
+        max_distance = max_distance.max((empirical - fitted).abs());
+    }
+
+    max_distance
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::{
+        hurwitz_zeta_,
+        PowerLawOptions,
+        RankFrequencyCurve,
+    };
+
+    use crate::containers::{
+        FrequencyMap,
+        UnicodePointMap,
+    };
+
+    use std::f64::consts::PI;
+
+
+    #[test]
+    fn TEST_hurwitz_zeta_() {
+        assert!((PI * PI / 6.0 - hurwitz_zeta_(2.0, 1.0)).abs() < 1e-12);
+        assert!((PI * PI / 6.0 - 1.0 - hurwitz_zeta_(2.0, 2.0)).abs() < 1e-12);
+        assert!((1.2020569031595942 - hurwitz_zeta_(3.0, 1.0)).abs() < 1e-12);
+        assert!((PI.powi(4) / 90.0 - hurwitz_zeta_(4.0, 1.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn TEST_RankFrequencyCurve_From_AND_log_log_points() {
+        let fm = FrequencyMap::<&str>::from([
+            // insert list
+            ("a", 100),
+            ("b", 10),
+            ("c", -3),
+            ("d", 1),
+        ]);
+
+        let curve = RankFrequencyCurve::from(&fm);
+
+        assert_eq!(&[100, 10, 1], curve.frequencies());
+
+        let points : Vec<_> = curve.log_log_points().collect();
+
+        assert_eq!(3, points.len());
+        assert!((points[0].0 - 0.0).abs() < 1e-12 && (points[0].1 - 2.0).abs() < 1e-12);
+        assert!((points[1].0 - 2f64.log10()).abs() < 1e-12 && (points[1].1 - 1.0).abs() < 1e-12);
+        assert!((points[2].0 - 3f64.log10()).abs() < 1e-12 && (points[2].1 - 0.0).abs() < 1e-12);
+
+        let upm = UnicodePointMap::from_iter("mississippi".chars());
+
+        assert_eq!(&[4, 4, 2, 1], RankFrequencyCurve::from(&upm).frequencies());
+    }
+
+    #[test]
+    fn TEST_RankFrequencyCurve_fit_power_law_RECOVERS_EXPONENT() {
+        // the expected numbers of records having each count, in a sample of
+        // 100,000 records from a discrete power law with alpha = 2.5
+        let zeta = hurwitz_zeta_(2.5, 1.0);
+        let counts = (1..=10_000_isize).flat_map(|x| {
+            let n = (100_000.0 * (x as f64).powf(-2.5) / zeta).round() as usize;
+
+            std::iter::repeat_n(x, n)
+        });
+
+        let curve = RankFrequencyCurve::from_counts(counts);
+        let fit = curve.fit_power_law(&PowerLawOptions::default()).unwrap();
+
+        assert!((fit.alpha - 2.5).abs() < 0.05, "alpha={}", fit.alpha);
+        assert!(fit.ks_statistic < 0.01);
+        assert!(fit.tail_len >= 10);
+        assert!(fit.log_likelihood < 0.0);
+        assert!((fit.zipf_exponent() - 1.0 / 1.5).abs() < 0.05);
+
+        let restricted = curve
+            .fit_power_law(&PowerLawOptions {
+                min_tail_len : 10,
+                max_x_min :    Some(1),
+            })
+            .unwrap();
+
+        assert_eq!(1, restricted.x_min);
+        assert_eq!(curve.len(), restricted.tail_len);
+    }
+
+    #[test]
+    fn TEST_RankFrequencyCurve_fit_power_law_INSUFFICIENT_DATA() {
+        let curve = RankFrequencyCurve::from_counts([5, 3, 1]);
+
+        assert_eq!(None, curve.fit_power_law(&PowerLawOptions::default()));
+        assert_eq!(None, RankFrequencyCurve::from_counts([]).fit_power_law(&PowerLawOptions::default()));
+        assert!(curve
+            .fit_power_law(&PowerLawOptions {
+                min_tail_len : 2,
+                max_x_min :    None,
+            })
+            .is_some());
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
@@ -3,4 +3,8 @@
 use crate::macros::declare_and_publish;
 
 
+declare_and_publish!(ngrams, ngrams, padded_ngrams, padded_vec_ngrams, vec_ngrams, NGrams, VecNGrams);
+declare_and_publish!(tokenize, borrowed_word_frequencies, tokenize, word_frequencies, TokenizeMode, TokenizerOptions, Tokens);
+
+
 // ///////////////////////////// end of file //////////////////////////// //
@@ -0,0 +1,371 @@
+// utils/ngrams.rs : `NGrams`, `VecNGrams`
+
+use alloc::{
+    collections::VecDeque,
+    vec::Vec,
+};
+
+use core::{
+    fmt as std_fmt,
+    iter as std_iter,
+};
+
+
+/// Sliding-window state shared by [`NGrams`] and [`VecNGrams`].
+struct Windows<I : Iterator> {
+    /// The source of tokens.
+    iter :     std_iter::Fuse<I>,
+    /// The current window, which holds at most `n` tokens.
+    buf :      VecDeque<I::Item>,
+    /// The window size.
+    n :        usize,
+    /// The padding symbol, if any.
+    pad :      Option<I::Item>,
+    /// The number of trailing padding symbols yet to be introduced.
+    trailing : usize,
+    /// Indicates whether any token has been obtained from `iter`.
+    seen :     bool,
+}
+
+impl<I> Windows<I>
+where
+    I : Iterator,
+    I::Item : Clone,
+{
+    fn new(
+        iter : I,
+        n : usize,
+        pad : Option<I::Item>,
+    ) -> Self {
+        let leading = if pad.is_some() { n.saturating_sub(1) } else { 0 };
+        let buf = match &pad {
+            Some(pad) => std_iter::repeat_n(pad.clone(), leading).collect(),
+            None => VecDeque::with_capacity(n),
+        };
+
+        Self {
+            iter : iter.fuse(),
+            buf,
+            n,
+            pad,
+            trailing : leading,
+            seen : false,
+        }
+    }
+
+    /// Slides the window along by one, returning `false` if there are no
+    /// more windows.
+    fn advance_(&mut self) -> bool {
+        if 0 == self.n {
+            return false;
+        }
+
+        loop {
+            let next = match self.iter.next() {
+                Some(token) => {
+                    self.seen = true;
+
+                    token
+                },
+                None => {
+                    match &self.pad {
+                        Some(pad) if self.seen && 0 != self.trailing => {
+                            self.trailing -= 1;
+
+                            pad.clone()
+                        },
+                        _ => return false,
+                    }
+                },
+            };
+
+            if self.buf.len() == self.n {
+                self.buf.pop_front();
+            }
+
+            self.buf.push_back(next);
+
+            if self.buf.len() == self.n {
+                return true;
+            }
+        }
+    }
+}
+
+impl<I> Clone for Windows<I>
+where
+    I : Iterator + Clone,
+    I::Item : Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            iter :     self.iter.clone(),
+            buf :      self.buf.clone(),
+            n :        self.n,
+            pad :      self.pad.clone(),
+            trailing : self.trailing,
+            seen :     self.seen,
+        }
+    }
+}
+
+impl<I> std_fmt::Debug for Windows<I>
+where
+    I : Iterator + std_fmt::Debug,
+    I::Item : std_fmt::Debug,
+{
+    fn fmt(
+        &self,
+        f : &mut std_fmt::Formatter<'_>,
+    ) -> std_fmt::Result {
+        f.debug_struct("Windows")
+            .field("iter", &self.iter)
+            .field("buf", &self.buf)
+            .field("n", &self.n)
+            .field("pad", &self.pad)
+            .field("trailing", &self.trailing)
+            .field("seen", &self.seen)
+            .finish()
+    }
+}
+
+/// Implements `Clone` and `Debug` for an iterator type that wraps `Windows`.
+macro_rules! impl_windows_wrapper_traits {
+    ($type_name:ident $(, $n:ident)?) => {
+        impl<I $(, const $n : usize)?> Clone for $type_name<I $(, $n)?>
+        where
+            I : Iterator + Clone,
+            I::Item : Clone,
+        {
+            fn clone(&self) -> Self {
+                Self {
+                    windows : self.windows.clone(),
+                }
+            }
+        }
+
+        impl<I $(, const $n : usize)?> std_fmt::Debug for $type_name<I $(, $n)?>
+        where
+            I : Iterator + std_fmt::Debug,
+            I::Item : std_fmt::Debug,
+        {
+            fn fmt(
+                &self,
+                f : &mut std_fmt::Formatter<'_>,
+            ) -> std_fmt::Result {
+                f.debug_struct(stringify!($type_name))
+                    .field("windows", &self.windows)
+                    .finish()
+            }
+        }
+    };
+}
+
+
+/// An iterator over the fixed-size windows of `N` consecutive tokens from
+/// an underlying iterator, obtained from [`ngrams()`] or
+/// [`padded_ngrams()`].
+///
+/// Each window is yielded as an array, so no allocation is performed per
+/// window, and a [`FrequencyMap<[T; N]>`](crate::containers::FrequencyMap)
+/// may be built directly from the iterator.
+///
+/// # Examples
+///
+/// ```
+/// use collect_rs::{
+///     containers::FrequencyMap,
+///     utils::ngrams,
+/// };
+///
+/// let fm : FrequencyMap<[char; 2]> = ngrams::<2, _>("banana".chars()).collect();
+///
+/// assert_eq!(2, fm[&['a', 'n']]);
+/// assert_eq!(2, fm[&['n', 'a']]);
+/// assert_eq!(1, fm[&['b', 'a']]);
+/// ```
+pub struct NGrams<I : Iterator, const N : usize> {
+    windows : Windows<I>,
+}
+
+impl<I, const N : usize> Iterator for NGrams<I, N>
+where
+    I : Iterator,
+    I::Item : Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.windows.advance_() {
+            let buf = &self.windows.buf;
+
+            Some(core::array::from_fn(|i| buf[i].clone()))
+        } else {
+            None
+        }
+    }
+}
+
+impl_windows_wrapper_traits!(NGrams, N);
+
+
+/// An iterator over the windows of `n` consecutive tokens from an
+/// underlying iterator, where `n` is determined at runtime, obtained from
+/// [`vec_ngrams()`] or [`padded_vec_ngrams()`].
+///
+/// Each window is yielded as a new [`Vec`]; where the window size is known
+/// at compile time, [`NGrams`] should be preferred.
+pub struct VecNGrams<I : Iterator> {
+    windows : Windows<I>,
+}
+
+impl<I> Iterator for VecNGrams<I>
+where
+    I : Iterator,
+    I::Item : Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.windows.advance_() {
+            Some(self.windows.buf.iter().cloned().collect())
+        } else {
+            None
+        }
+    }
+}
+
+impl_windows_wrapper_traits!(VecNGrams);
+
+
+/// Obtains an iterator over the windows of `N` consecutive tokens of
+/// `iter`. No windows are yielded if there are fewer than `N` tokens, or
+/// if `N` is 0.
+pub fn ngrams<const N : usize, I>(iter : I) -> NGrams<I::IntoIter, N>
+where
+    I : IntoIterator,
+    I::Item : Clone,
+{
+    NGrams {
+        windows : Windows::new(iter.into_iter(), N, None),
+    }
+}
+
+/// Obtains an iterator over the windows of `N` consecutive tokens of
+/// `iter`, as if it were preceded and followed by `N - 1` instances of
+/// `pad`. No windows are yielded if there are no tokens, or if `N` is 0.
+pub fn padded_ngrams<const N : usize, I>(
+    iter : I,
+    pad : I::Item,
+) -> NGrams<I::IntoIter, N>
+where
+    I : IntoIterator,
+    I::Item : Clone,
+{
+    NGrams {
+        windows : Windows::new(iter.into_iter(), N, Some(pad)),
+    }
+}
+
+/// Obtains an iterator over the windows of `n` consecutive tokens of
+/// `iter`. No windows are yielded if there are fewer than `n` tokens, or
+/// if `n` is 0.
+pub fn vec_ngrams<I>(
+    iter : I,
+    n : usize,
+) -> VecNGrams<I::IntoIter>
+where
+    I : IntoIterator,
+    I::Item : Clone,
+{
+    VecNGrams {
+        windows : Windows::new(iter.into_iter(), n, None),
+    }
+}
+
+/// Obtains an iterator over the windows of `n` consecutive tokens of
+/// `iter`, as if it were preceded and followed by `n - 1` instances of
+/// `pad`. No windows are yielded if there are no tokens, or if `n` is 0.
+pub fn padded_vec_ngrams<I>(
+    iter : I,
+    n : usize,
+    pad : I::Item,
+) -> VecNGrams<I::IntoIter>
+where
+    I : IntoIterator,
+    I::Item : Clone,
+{
+    VecNGrams {
+        windows : Windows::new(iter.into_iter(), n, Some(pad)),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::{
+        ngrams,
+        padded_ngrams,
+        padded_vec_ngrams,
+        vec_ngrams,
+    };
+
+    use crate::containers::FrequencyMap;
+
+
+    #[test]
+    fn TEST_ngrams_1() {
+        assert_eq!(vec![[1, 2], [2, 3], [3, 4]], ngrams::<2, _>([1, 2, 3, 4]).collect::<Vec<_>>());
+        assert_eq!(vec![[1, 2, 3, 4]], ngrams::<4, _>([1, 2, 3, 4]).collect::<Vec<_>>());
+        assert_eq!(0, ngrams::<5, _>([1, 2, 3, 4]).count());
+        assert_eq!(0, ngrams::<0, _>([1, 2, 3, 4]).count());
+        assert_eq!(vec![['a'], ['b']], ngrams::<1, _>("ab".chars()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn TEST_padded_ngrams_1() {
+        assert_eq!(
+            vec![['^', '^', 'a'], ['^', 'a', 'b'], ['a', 'b', '^'], ['b', '^', '^']],
+            padded_ngrams::<3, _>("ab".chars(), '^').collect::<Vec<_>>()
+        );
+        assert_eq!(vec![['x']], padded_ngrams::<1, _>("x".chars(), '^').collect::<Vec<_>>());
+        assert_eq!(0, padded_ngrams::<3, _>("".chars(), '^').count());
+    }
+
+    #[test]
+    fn TEST_vec_ngrams_1() {
+        let words = "to be or not to be".split(' ');
+
+        assert_eq!(
+            vec![vec!["to", "be"], vec!["be", "or"], vec!["or", "not"], vec!["not", "to"], vec!["to", "be"]],
+            vec_ngrams(words.clone(), 2).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![vec!["", "to"], vec!["to", ""]],
+            padded_vec_ngrams(["to"], 2, "").collect::<Vec<_>>()
+        );
+        assert_eq!(0, vec_ngrams(words, 0).count());
+    }
+
+    #[test]
+    fn TEST_ngrams_INTO_FrequencyMap() {
+        let text = "the thin thing";
+
+        let bigrams : FrequencyMap<[char; 2]> = ngrams::<2, _>(text.chars()).collect();
+        let trigrams : FrequencyMap<Vec<char>> = padded_vec_ngrams(text.chars(), 3, ' ').collect();
+
+        assert_eq!(13, bigrams.total());
+        assert_eq!(3, bigrams[&['t', 'h']]);
+        assert_eq!(2, bigrams[&['i', 'n']]);
+
+        assert_eq!(16, trigrams.total());
+        assert_eq!(2, trigrams[&vec!['t', 'h', 'i']]);
+        assert_eq!(1, trigrams[&vec![' ', ' ', 't']]);
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+
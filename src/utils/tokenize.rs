@@ -0,0 +1,397 @@
+// utils/tokenize.rs : `TokenizeMode`, `TokenizerOptions`, `Tokens`
+
+use crate::containers::FrequencyMap;
+
+use alloc::{
+    borrow::Cow,
+    string::String,
+};
+
+use core::iter;
+
+
+/// The means by which [`tokenize()`] divides text into tokens.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub enum TokenizeMode {
+    /// Tokens are the runs of non-whitespace characters.
+    #[default]
+    Whitespace,
+    /// Tokens are divided at (an approximation of) the word boundaries of
+    /// Unicode Standard Annex #29: runs of letters and digits, which may
+    /// contain an apostrophe, full stop, or colon between letters, or a
+    /// comma or full stop between digits, are words; every other
+    /// non-whitespace character is a token in its own right.
+    WordBoundary,
+    /// Tokens are the runs of alphanumeric characters.
+    AlphanumericRun,
+}
+
+/// Options that control [`tokenize()`].
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub struct TokenizerOptions {
+    /// The means by which text is divided into tokens.
+    pub mode :              TokenizeMode,
+    /// Whether tokens are converted to lowercase. Defaults to `false`.
+    pub lowercase :         bool,
+    /// Whether leading and trailing punctuation (and symbols) are stripped
+    /// from each token. Defaults to `false`.
+    pub strip_punctuation : bool,
+    /// Whether leading and trailing digits are stripped from each token.
+    /// Defaults to `false`.
+    pub strip_digits :      bool,
+    /// The minimum length, in characters, of a token after stripping;
+    /// shorter tokens are discarded. Defaults to 1.
+    pub min_length :        usize,
+}
+
+impl Default for TokenizerOptions {
+    fn default() -> Self {
+        Self {
+            mode :              TokenizeMode::default(),
+            lowercase :         false,
+            strip_punctuation : false,
+            strip_digits :      false,
+            min_length :        1,
+        }
+    }
+}
+
+
+/// An iterator over the tokens of a string, obtained from [`tokenize()`].
+///
+/// Tokens borrow from the string unless they have been lowercased.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct Tokens<'a> {
+    /// The remaining text.
+    text :    &'a str,
+    /// The options.
+    options : TokenizerOptions,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Cow<'a, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.next_slice_()?;
+
+        // not only uppercase letters change, e.g. titlecase 'ǅ' -> 'ǆ'
+        if self.options.lowercase && token.chars().any(|c| c.to_lowercase().ne(iter::once(c))) {
+            Some(Cow::Owned(token.to_lowercase()))
+        } else {
+            Some(Cow::Borrowed(token))
+        }
+    }
+}
+
+// Implementation
+
+impl<'a> Tokens<'a> {
+    /// Obtains the next token, after stripping and length filtering but
+    /// before lowercasing.
+    fn next_slice_(&mut self) -> Option<&'a str> {
+        loop {
+            let raw = self.next_raw_()?;
+            let token = raw.trim_matches(|c : char| {
+                (self.options.strip_punctuation && is_punctuation_(c)) || (self.options.strip_digits && c.is_numeric())
+            });
+
+            if !token.is_empty() && token.chars().count() >= self.options.min_length {
+                return Some(token);
+            }
+        }
+    }
+
+    /// Obtains the next token according to the mode.
+    fn next_raw_(&mut self) -> Option<&'a str> {
+        let text = match self.options.mode {
+            TokenizeMode::AlphanumericRun => self.text.trim_start_matches(|c : char| !c.is_alphanumeric()),
+            _ => self.text.trim_start(),
+        };
+
+        let end = match self.options.mode {
+            TokenizeMode::Whitespace => text.find(char::is_whitespace).unwrap_or(text.len()),
+            TokenizeMode::WordBoundary => word_boundary_end_(text).unwrap_or(0),
+            TokenizeMode::AlphanumericRun => text.find(|c : char| !c.is_alphanumeric()).unwrap_or(text.len()),
+        };
+
+        if 0 == end {
+            self.text = "";
+
+            None
+        } else {
+            let (token, rest) = text.split_at(end);
+
+            self.text = rest;
+
+            Some(token)
+        }
+    }
+}
+
+fn is_punctuation_(c : char) -> bool {
+    !c.is_alphanumeric() && !c.is_whitespace() && !c.is_control()
+}
+
+fn is_word_char_(c : char) -> bool {
+    c.is_alphanumeric() || '_' == c
+}
+
+/// Obtains the byte length of the word-boundary token at the start of
+/// `text`, which has no leading whitespace, or `None` if `text` is empty.
+fn word_boundary_end_(text : &str) -> Option<usize> {
+    let mut chars = text.char_indices().peekable();
+
+    let (_, first) = chars.next()?;
+
+    if !is_word_char_(first) {
+        return Some(first.len_utf8());
+    }
+
+    let mut prev = first;
+    let mut end = first.len_utf8();
+
+    while let Some(&(ix, c)) = chars.peek() {
+        if is_word_char_(c) {
+            prev = c;
+            end = ix + c.len_utf8();
+
+            chars.next();
+
+            continue;
+        }
+
+        let is_mid_letter = matches!(c, '\'' | '\u{2019}' | '.' | ':' | '\u{00B7}');
+        let is_mid_num = matches!(c, ',' | '.' | ';');
+
+        let follower = text[ix + c.len_utf8() ..].chars().next();
+
+        let joins = match follower {
+            Some(next) => {
+                (is_mid_letter && prev.is_alphabetic() && next.is_alphabetic())
+                    || (is_mid_num && prev.is_numeric() && next.is_numeric())
+            },
+            None => false,
+        };
+
+        if !joins {
+            break;
+        }
+
+        chars.next();
+    }
+
+    Some(end)
+}
+
+
+/// Obtains an iterator over the tokens of `text`, according to the given
+/// `options`.
+///
+/// # Examples
+///
+/// ```
+/// use collect_rs::utils::{
+///     tokenize,
+///     TokenizeMode,
+///     TokenizerOptions,
+/// };
+///
+/// let options = TokenizerOptions {
+///     mode : TokenizeMode::WordBoundary,
+///     lowercase : true,
+///     strip_punctuation : true,
+///     ..Default::default()
+/// };
+///
+/// let tokens : Vec<_> = tokenize("Don't panic, Arthur!", &options).collect();
+///
+/// assert_eq!(vec!["don't", "panic", "arthur"], tokens);
+/// ```
+pub fn tokenize<'a>(
+    text : &'a str,
+    options : &TokenizerOptions,
+) -> Tokens<'a> {
+    Tokens {
+        text,
+        options : *options,
+    }
+}
+
+/// Obtains the frequencies of the tokens of `text`, according to the
+/// given `options`.
+pub fn word_frequencies(
+    text : &str,
+    options : &TokenizerOptions,
+) -> FrequencyMap<String> {
+    let mut fm = FrequencyMap::new();
+
+    for token in tokenize(text, options) {
+        fm.push_borrowed(token.as_ref());
+    }
+
+    fm
+}
+
+/// Obtains the frequencies of the tokens of `text`, according to the
+/// given `options`, where each key borrows from `text` unless it has been
+/// lowercased, as the tokens obtained from [`tokenize()`].
+pub fn borrowed_word_frequencies<'a>(
+    text : &'a str,
+    options : &TokenizerOptions,
+) -> FrequencyMap<Cow<'a, str>> {
+    let mut fm = FrequencyMap::new();
+
+    for token in tokenize(text, options) {
+        fm.push(token);
+    }
+
+    fm
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::{
+        borrowed_word_frequencies,
+        tokenize,
+        word_frequencies,
+        TokenizeMode,
+        TokenizerOptions,
+    };
+
+    use std::borrow::Cow;
+
+
+    const TEXT : &str = "  The cat's 2nd mat, at 3.14pm: the CAT sat!\t(e-mail) ";
+
+    fn tokens(
+        text : &str,
+        options : &TokenizerOptions,
+    ) -> Vec<String> {
+        tokenize(text, options).map(|token| token.into_owned()).collect()
+    }
+
+    #[test]
+    fn TEST_tokenize_Whitespace() {
+        let options = TokenizerOptions::default();
+
+        assert_eq!(
+            vec!["The", "cat's", "2nd", "mat,", "at", "3.14pm:", "the", "CAT", "sat!", "(e-mail)"],
+            tokens(TEXT, &options)
+        );
+        assert!(tokens("", &options).is_empty());
+        assert!(tokens(" \t\n ", &options).is_empty());
+    }
+
+    #[test]
+    fn TEST_tokenize_WordBoundary() {
+        let options = TokenizerOptions {
+            mode : TokenizeMode::WordBoundary,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![
+                "The", "cat's", "2nd", "mat", ",", "at", "3.14pm", ":", "the", "CAT", "sat", "!", "(", "e", "-", "mail", ")",
+            ],
+            tokens(TEXT, &options)
+        );
+        assert_eq!(vec!["1,000", ",", "ok", "."], tokens("1,000, ok.", &options));
+    }
+
+    #[test]
+    fn TEST_tokenize_AlphanumericRun() {
+        let options = TokenizerOptions {
+            mode : TokenizeMode::AlphanumericRun,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec!["The", "cat", "s", "2nd", "mat", "at", "3", "14pm", "the", "CAT", "sat", "e", "mail"],
+            tokens(TEXT, &options)
+        );
+        assert!(tokens("...", &options).is_empty());
+    }
+
+    #[test]
+    fn TEST_tokenize_OPTIONS() {
+        let options = TokenizerOptions {
+            lowercase : true,
+            strip_punctuation : true,
+            strip_digits : true,
+            min_length : 3,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec!["the", "cat's", "mat", "the", "cat", "sat", "e-mail"],
+            tokens(TEXT, &options)
+        );
+    }
+
+    #[test]
+    fn TEST_tokenize_lowercase_TITLECASE() {
+        let options = TokenizerOptions {
+            lowercase : true,
+            ..Default::default()
+        };
+
+        // 'ǅ' is titlecase, and neither uppercase nor lowercase
+        assert!(!'ǅ'.is_uppercase());
+        assert_eq!(vec!["ǆemal", "ǉubljana", "abc"], tokens("ǅemal ǈubljana abc", &options));
+        assert!(matches!(tokenize("abc", &options).next(), Some(Cow::Borrowed("abc"))));
+    }
+
+    #[test]
+    fn TEST_word_frequencies() {
+        let options = TokenizerOptions {
+            mode : TokenizeMode::AlphanumericRun,
+            lowercase : true,
+            ..Default::default()
+        };
+
+        let fm = word_frequencies(TEXT, &options);
+
+        assert_eq!(13, fm.total());
+        assert_eq!(2, fm["the"]);
+        assert_eq!(2, fm["cat"]);
+
+        let fm = borrowed_word_frequencies(TEXT, &options);
+
+        assert_eq!(13, fm.total());
+        assert_eq!(2, fm["the"]);
+        assert_eq!(2, fm["cat"]);
+        // "The" is lowercased on its first occurrence, and so owned
+        assert!(matches!(fm.iter().find(|(key, _)| "the" == *key), Some((Cow::Owned(_), 2))));
+        assert!(matches!(fm.iter().find(|(key, _)| "mat" == *key), Some((Cow::Borrowed("mat"), 1))));
+
+        let options = TokenizerOptions {
+            lowercase : false,
+            ..options
+        };
+
+        let fm = borrowed_word_frequencies(TEXT, &options);
+
+        assert_eq!(13, fm.total());
+        assert_eq!(1, fm["the"]);
+        assert_eq!(1, fm["The"]);
+        assert_eq!(1, fm["CAT"]);
+        assert!(fm.iter().all(|(key, _)| matches!(key, Cow::Borrowed(_))));
+    }
+}
+
+
+// ///////////////////////////// end of file //////////////////////////// //
+